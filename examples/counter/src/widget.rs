@@ -37,12 +37,9 @@ impl Widget for Counter {
     // build a counter widget with tap gesture and white background
     fn build(&self, context: &mut WidgetContext, build: &mut BuildContext) {
         let state = context.state::<CounterState>().unwrap();
+        let handle = build.handle();
         build.add_child(
-            Gesture::new(
-                0 /* unique event id within a scope of the widget */,
-                build.event_emitter(),
-                TapGesture::default(),
-                None,
+            Gesture::new_child(
                 Decoration::new(
                     Color::WHITE,
                     None,
@@ -51,16 +48,14 @@ impl Widget for Counter {
                         Text::new_text(format!("Counter {}", state.counter).as_str()),
                     ),
                 ),
-            ),
+            )
+            // increment the counter on a single tap and rebuild
+            .on_tap(move || {
+                handle.update_state(|state: &mut CounterState, event| {
+                    state.counter += 1;
+                    event.mark_need_build();
+                })
+            }),
         );
     }
-
-    // handle a single tap
-    fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
-        if let Event::Tap(_) = event.get() {
-            let state = context.state_mut::<CounterState>().unwrap();
-            state.counter += 1;
-            event.mark_need_build();
-        }
-    }
 }
@@ -33,39 +33,25 @@ fn main() {
     App::new()
         .with_size((1020, 640))
         .with_title("Terra Mach - Dashboard")
-        .run(DefaultTextStyle::new(
-            TextStyle::default()
-                .with_color(Color::new(0xFFFFFFFF))
-                .with_font_families(&["Helvetica Neue"]),
-            Decoration::new(
-                Color::new(0xFF1E2429),
-                None,
-                Row::default()
-                    .with_child(Sidebar::new())
-                    .with_flex_child(
-                        1,
-                        Column::default()
-                            .with_child(Topbar::new())
-                            .with_flex_child(
-                                1,
-                                Scrollable::new(
-                                    ScrollDirection::Vertical,
-                                    Padding::new_all(
-                                        20.0,
-                                        Column::default()
-                                            .with_child(Navigation::new())
-                                            .with_child(Constrained::new_empty(Constraints::new_tight(
-                                                Size::new_unbound_width(20.0),
-                                            )))
-                                            .with_child(Metrics::new())
-                                            .with_child(Constrained::new_empty(Constraints::new_tight(
-                                                Size::new_unbound_width(20.0),
-                                            )))
-                                            .with_child(Sensors::new()),
-                                    ),
-                                ),
-                            ),
-                    ),
-            ),
-        ));
+        .run(view! {
+            DefaultTextStyle(
+                TextStyle::default()
+                    .with_color(Color::new(0xFFFFFFFF))
+                    .with_font_families(&["Helvetica Neue"]),
+            ) => Decoration(Color::new(0xFF1E2429), None) => Row {
+                Sidebar::new(),
+                #[flex(1)]
+                Column {
+                    Topbar::new(),
+                    #[flex(1)]
+                    Scrollable(ScrollDirection::Vertical) => Padding::new_all(20.0) => Column {
+                        Navigation::new(),
+                        Constrained::new_empty(Constraints::new_tight(Size::new_unbound_width(20.0))),
+                        Metrics::new(),
+                        Constrained::new_empty(Constraints::new_tight(Size::new_unbound_width(20.0))),
+                        Sensors::new(),
+                    }
+                }
+            }
+        });
 }
@@ -138,6 +138,13 @@ impl Widget for Map {
         state.channel.send(MapCommand::JumpTo(self.camera.clone()));
     }
 
+    fn unmount(&self, context: &mut WidgetContext) {
+        if let Some(state) = context.state_mut::<MapState>() {
+            state.channel.deactivate();
+        }
+        context.set_state::<MapState>(None);
+    }
+
     fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
         let state = context.state_mut::<MapState>().unwrap();
         match event.get() {
@@ -103,9 +103,10 @@ impl Widget for Metric {
     fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
         match event.get() {
             Event::Frame(_) => {
+                let clock = context.clock();
                 let state = context.state_mut::<MetricState>().unwrap();
                 if let Some(animation) = &mut state.animation {
-                    if animation.advance() {
+                    if animation.advance(&clock) {
                         context.request_frame();
                     }
                     event.mark_need_build();
@@ -62,9 +62,10 @@ impl Widget for Sensor {
     fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
         match event.get() {
             Event::Frame(_) => {
+                let clock = context.clock();
                 let state = context.state_mut::<SensorState>().unwrap();
                 if let Some(animation) = &mut state.animation {
-                    if animation.advance() {
+                    if animation.advance(&clock) {
                         context.request_frame();
                     }
                     event.mark_need_build();
@@ -16,6 +16,8 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
+use std::convert::TryFrom;
+
 use terramach::graphics::{Color, Size};
 use terramach::widgets::*;
 use terramach::*;
@@ -130,14 +132,15 @@ impl Widget for Sidebar {
         match event.get() {
             Event::Tap(id) => {
                 let state = context.state_mut::<SidebarState>().unwrap();
-                match id.into() {
-                    Button::Speed | Button::Help | Button::Settings | Button::Earth => {
-                        state.selected_button = id.into();
-                        event.mark_need_build();
-                    }
-                    Button::Power => {
+                match Button::try_from(id) {
+                    Ok(Button::Power) => {
                         println!("Shutdown!");
                     }
+                    Ok(button) => {
+                        state.selected_button = button;
+                        event.mark_need_build();
+                    }
+                    Err(_) => {}
                 }
             }
             _ => {}
@@ -16,6 +16,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
+use std::convert::TryFrom;
 use std::time::Duration;
 
 use terramach::graphics::*;
@@ -90,9 +91,10 @@ impl Widget for Tab {
     fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
         match event.get() {
             Event::Frame(_) => {
+                let clock = context.clock();
                 let state = context.state_mut::<TabState>().unwrap();
                 if let Some(animation) = &mut state.animation {
-                    if animation.advance() {
+                    if animation.advance(&clock) {
                         event.mark_need_paint();
                     } else {
                         state.animation = None;
@@ -258,9 +260,11 @@ impl Widget for Topbar {
     fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
         match event.get() {
             Event::Tap(id) => {
-                let state = context.state_mut::<TopbarState>().unwrap();
-                state.selected_button = id.into();
-                event.mark_need_build();
+                if let Ok(button) = Button::try_from(id) {
+                    let state = context.state_mut::<TopbarState>().unwrap();
+                    state.selected_button = button;
+                    event.mark_need_build();
+                }
             }
             _ => {}
         }
@@ -29,3 +29,10 @@ mod android_context;
 
 #[cfg(target_os = "android")]
 pub use android_context::*;
+
+#[cfg(target_os = "linux")]
+#[path = "linux_context.rs"]
+mod linux_context;
+
+#[cfg(target_os = "linux")]
+pub use linux_context::*;
@@ -0,0 +1,179 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::os::raw::c_int;
+use std::ptr::null_mut;
+use std::rc::Rc;
+
+use x11::glx;
+use x11::xlib;
+
+struct GLXContext {
+    display: *mut xlib::Display,
+    context: glx::GLXContext,
+    draw_drawable: glx::GLXDrawable,
+    read_drawable: glx::GLXDrawable,
+    owner: bool,
+}
+
+impl GLXContext {
+    pub unsafe fn native(&self) -> glx::GLXContext {
+        self.context
+    }
+}
+
+impl Drop for GLXContext {
+    fn drop(&mut self) {
+        unsafe {
+            glx::glXMakeContextCurrent(self.display, 0, 0, null_mut());
+            if self.owner {
+                glx::glXDestroyContext(self.display, self.context);
+            }
+        }
+    }
+}
+
+pub struct Context {
+    inner: Rc<GLXContext>,
+    guard: Option<ContextGuard>,
+}
+
+impl Context {
+    pub fn current() -> Option<Self> {
+        unsafe {
+            let context = glx::glXGetCurrentContext();
+            let display = glx::glXGetCurrentDisplay();
+            if context.is_null() || display.is_null() {
+                None
+            } else {
+                Some(Context {
+                    inner: Rc::new(GLXContext {
+                        display,
+                        context,
+                        draw_drawable: glx::glXGetCurrentDrawable(),
+                        read_drawable: glx::glXGetCurrentReadDrawable(),
+                        owner: false,
+                    }),
+                    guard: None,
+                })
+            }
+        }
+    }
+
+    pub fn new_shared(&mut self) -> Self {
+        unsafe {
+            let display = self.inner.display;
+            let mut config_id: c_int = 0;
+            let error = glx::glXQueryContext(display, self.inner.native(), glx::GLX_FBCONFIG_ID, &mut config_id);
+            debug_assert!(error == 0);
+            let mut screen: c_int = 0;
+            let error = glx::glXQueryContext(display, self.inner.native(), glx::GLX_SCREEN, &mut screen);
+            debug_assert!(error == 0);
+            let attributes = [glx::GLX_FBCONFIG_ID, config_id, 0];
+            let mut count = 0;
+            let configs = glx::glXChooseFBConfig(display, screen, attributes.as_ptr(), &mut count);
+            debug_assert!(!configs.is_null() && count > 0);
+            let context = glx::glXCreateNewContext(
+                display,
+                *configs,
+                glx::GLX_RGBA_TYPE,
+                self.inner.native(),
+                xlib::True,
+            );
+            xlib::XFree(configs as *mut _);
+            debug_assert!(!context.is_null());
+            Context {
+                inner: Rc::new(GLXContext {
+                    display,
+                    context,
+                    draw_drawable: self.inner.draw_drawable,
+                    read_drawable: self.inner.read_drawable,
+                    owner: true,
+                }),
+                guard: None,
+            }
+        }
+    }
+
+    pub fn lock_current(&mut self) -> ContextGuard {
+        ContextGuard::new(self)
+    }
+
+    pub fn make_current(&mut self) {
+        if self.guard.is_none() {
+            self.guard = self.lock_current().into();
+        }
+    }
+
+    pub fn clear_current(&mut self) {
+        self.guard = None;
+    }
+}
+
+impl Clone for Context {
+    fn clone(&self) -> Self {
+        Context {
+            inner: self.inner.clone(),
+            guard: None,
+        }
+    }
+}
+
+pub struct ContextGuard {
+    display: *mut xlib::Display,
+    current_context: glx::GLXContext,
+    current_draw_drawable: glx::GLXDrawable,
+    current_read_drawable: glx::GLXDrawable,
+}
+
+impl ContextGuard {
+    fn new(context: &Context) -> Self {
+        unsafe {
+            let current_context = glx::glXGetCurrentContext();
+            let current_draw_drawable = glx::glXGetCurrentDrawable();
+            let current_read_drawable = glx::glXGetCurrentReadDrawable();
+            let result = glx::glXMakeContextCurrent(
+                context.inner.display,
+                context.inner.draw_drawable,
+                context.inner.read_drawable,
+                context.inner.native(),
+            );
+            debug_assert!(result == xlib::True);
+            ContextGuard {
+                display: context.inner.display,
+                current_context,
+                current_draw_drawable,
+                current_read_drawable,
+            }
+        }
+    }
+}
+
+impl Drop for ContextGuard {
+    fn drop(&mut self) {
+        unsafe {
+            let result = glx::glXMakeContextCurrent(
+                self.display,
+                self.current_draw_drawable,
+                self.current_read_drawable,
+                self.current_context,
+            );
+            debug_assert!(result == xlib::True);
+        }
+    }
+}
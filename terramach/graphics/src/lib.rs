@@ -18,6 +18,7 @@
 
 mod display;
 pub mod gl;
+pub mod raster;
 
 pub use display::*;
 pub use skia_safe::*;
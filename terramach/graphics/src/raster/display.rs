@@ -0,0 +1,126 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::sync::{Arc, Mutex};
+
+use crate::{Display as GrDisplay, ISize, Image, Size, Surface};
+
+#[derive(Clone, Default)]
+pub struct DisplaySnapshot {
+    image: Arc<Mutex<Option<Image>>>,
+}
+
+impl DisplaySnapshot {
+    pub fn new() -> Self {
+        DisplaySnapshot::default()
+    }
+
+    pub fn image(&self) -> Option<Image> {
+        self.image.lock().ok()?.clone()
+    }
+
+    fn set_image(&self, image: Image) {
+        if let Ok(mut current_image) = self.image.lock() {
+            current_image.replace(image);
+        }
+    }
+}
+
+// SAFETY: the snapshot only ever holds images taken from a raster surface. Such an image
+// is immutable CPU memory that is not bound to a GPU context, and Skia reference counts it
+// atomically, so it can be shared and dropped from any thread. Access to the slot itself
+// is guarded by the mutex.
+unsafe impl Send for DisplaySnapshot {}
+
+// SAFETY: see `Send` above.
+unsafe impl Sync for DisplaySnapshot {}
+
+pub struct Display {
+    size: Size,
+    pixel_ratio: f32,
+    surface: Option<Surface>,
+    snapshot: DisplaySnapshot,
+}
+
+impl Display {
+    pub fn new(size: impl Into<Size>, pixel_ratio: impl Into<Option<f32>>) -> Self {
+        Display {
+            size: size.into(),
+            pixel_ratio: pixel_ratio.into().unwrap_or(1.0),
+            surface: None,
+            snapshot: DisplaySnapshot::new(),
+        }
+    }
+
+    pub fn with_snapshot(mut self, snapshot: DisplaySnapshot) -> Self {
+        self.snapshot = snapshot;
+        self
+    }
+
+    pub fn snapshot(&self) -> DisplaySnapshot {
+        self.snapshot.clone()
+    }
+
+    fn pixel_size(&self) -> ISize {
+        self.scaled_size(self.size)
+    }
+
+    fn scaled_size(&self, size: Size) -> ISize {
+        Size::new(
+            size.width * self.pixel_ratio,
+            size.height * self.pixel_ratio,
+        ).to_ceil()
+    }
+}
+
+impl GrDisplay for Display {
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn resize(&mut self, size: Size) {
+        self.size = size;
+    }
+
+    fn pixel_ratio(&self) -> f32 {
+        self.pixel_ratio
+    }
+
+    fn new_surface(&mut self) -> Option<Surface> {
+        let mut surface = Surface::new_raster_n32_premul(self.pixel_size())?;
+        surface.canvas().scale((self.pixel_ratio, self.pixel_ratio));
+        self.surface = Some(surface.clone());
+        Some(surface)
+    }
+
+    fn new_offscreen_surface(&mut self, size: Size) -> Option<Surface> {
+        let mut surface = Surface::new_raster_n32_premul(self.scaled_size(size))?;
+        surface.canvas().scale((self.pixel_ratio, self.pixel_ratio));
+        Some(surface)
+    }
+
+    fn clear_current(&mut self) {}
+
+    fn make_current(&mut self) {}
+
+    fn present_current(&mut self) {
+        if let Some(surface) = &mut self.surface {
+            self.snapshot.set_image(surface.image_snapshot());
+        }
+    }
+}
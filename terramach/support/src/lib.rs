@@ -18,11 +18,18 @@
 
 extern crate proc_macro;
 
+mod view;
+
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
-use syn::{parse_macro_input, DeriveInput, AttributeArgs, ItemFn, NestedMeta, Meta, Lit};
+use syn::{
+    parse_macro_input, AttributeArgs, Data, DataStruct, DeriveInput, Expr, Field, Fields, GenericArgument,
+    ItemFn, Lit, Meta, NestedMeta, PathArguments, Type,
+};
 use quote::quote;
 
+use crate::view::View;
+
 #[proc_macro_attribute]
 pub fn noop_attribute(_: TokenStream, input: TokenStream) -> TokenStream {
     input
@@ -79,6 +86,188 @@ pub fn derive_partial_widget(input: TokenStream) -> TokenStream {
     TokenStream::from(expanded)
 }
 
+/// Composes a widget tree from a declarative description.
+///
+/// A widget is written as `Type(args, name = value) { children }`. Positional arguments go to
+/// `Type::new`, or `Type::default()` is used when there are none, and named arguments call the
+/// matching `with_name` setter. A path ending with a function, such as `Text::new_text("Hi")`,
+/// is called as is. `=> child` passes a single child as the last constructor argument, while
+/// `{ .. }` adds children through `with_child`, or `with_flex_child` when marked `#[flex(weight)]`.
+/// Children may also be `if` and `for` blocks, or arbitrary `{ expression }` widgets.
+#[proc_macro]
+pub fn view(input: TokenStream) -> TokenStream {
+    let view = parse_macro_input!(input as View);
+    TokenStream::from(view.expand())
+}
+
+#[proc_macro_derive(WidgetBuilder, attributes(builder))]
+pub fn derive_widget_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(DataStruct { fields: Fields::Named(fields), .. }) => &fields.named,
+        _ => {
+            return syn::Error::new_spanned(name, "WidgetBuilder supports only structs with named fields")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let mut arguments = Vec::new();
+    let mut initializers = Vec::new();
+    let mut setters = Vec::new();
+    for field in fields {
+        let options = match BuilderOptions::parse(field) {
+            Ok(options) => options,
+            Err(error) => return error.to_compile_error().into(),
+        };
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+
+        if (options.required || is_type(ty, "BoxedWidget")) && is_primitive(ty) {
+            arguments.push(quote! { #ident: #ty });
+            initializers.push(quote! { #ident });
+        } else if options.required || is_type(ty, "BoxedWidget") {
+            arguments.push(quote! { #ident: impl Into<#ty> });
+            initializers.push(quote! { #ident: #ident.into() });
+        } else if let Some(default) = &options.default {
+            initializers.push(quote! { #ident: #default });
+        } else {
+            initializers.push(quote! { #ident: Default::default() });
+        }
+
+        if options.skip || is_type(ty, "BoxedWidget") {
+            continue;
+        }
+        let setter = Ident::new(&format!("with_{}", ident), Span::call_site());
+        match (inner_type(ty, "Option"), inner_type(ty, "Vec")) {
+            (Some(inner), _) if options.strip_option || is_type(inner, "BoxedWidget") => {
+                setters.push(quote! {
+                    pub fn #setter(mut self, #ident: impl Into<#inner>) -> Self {
+                        self.#ident = Some(#ident.into());
+                        self
+                    }
+                });
+            }
+            (_, Some(inner)) if is_type(inner, "BoxedWidget") => {
+                let setter = if ident == "children" {
+                    Ident::new("with_child", Span::call_site())
+                } else {
+                    setter
+                };
+                setters.push(quote! {
+                    pub fn #setter(mut self, widget: impl Into<#inner>) -> Self {
+                        self.#ident.push(widget.into());
+                        self
+                    }
+                });
+            }
+            _ if is_primitive(ty) => {
+                setters.push(quote! {
+                    pub fn #setter(mut self, #ident: #ty) -> Self {
+                        self.#ident = #ident;
+                        self
+                    }
+                });
+            }
+            _ => {
+                setters.push(quote! {
+                    pub fn #setter(mut self, #ident: impl Into<#ty>) -> Self {
+                        self.#ident = #ident.into();
+                        self
+                    }
+                });
+            }
+        }
+    }
+
+    let expanded = quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            pub fn builder(#(#arguments),*) -> Self {
+                #name {
+                    #(#initializers),*
+                }
+            }
+
+            #(#setters)*
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+#[derive(Default)]
+struct BuilderOptions {
+    default: Option<Expr>,
+    required: bool,
+    skip: bool,
+    strip_option: bool,
+}
+
+impl BuilderOptions {
+    fn parse(field: &Field) -> syn::Result<Self> {
+        let mut options = BuilderOptions::default();
+        for attr in field.attrs.iter().filter(|attr| attr.path.is_ident("builder")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new_spanned(meta, "Expected #[builder(...)]")),
+            };
+            for nested in list.nested {
+                match nested {
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("required") => options.required = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("skip") => options.skip = true,
+                    NestedMeta::Meta(Meta::Path(path)) if path.is_ident("strip_option") => options.strip_option = true,
+                    NestedMeta::Meta(Meta::NameValue(value)) if value.path.is_ident("default") => {
+                        match &value.lit {
+                            Lit::Str(default) => options.default = Some(default.parse()?),
+                            lit => return Err(syn::Error::new_spanned(lit, "Expected a string with an expression")),
+                        }
+                    }
+                    nested => return Err(syn::Error::new_spanned(nested, "Unknown builder option")),
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+fn is_type(ty: &Type, name: &str) -> bool {
+    match ty {
+        Type::Path(path) => path.path.segments.last().map_or(false, |segment| segment.ident == name),
+        _ => false,
+    }
+}
+
+fn is_primitive(ty: &Type) -> bool {
+    const PRIMITIVES: &[&str] = &[
+        "bool", "f32", "f64", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize",
+    ];
+    match ty {
+        Type::Path(path) => path.path.get_ident().map_or(false, |ident| PRIMITIVES.iter().any(|name| ident == name)),
+        _ => false,
+    }
+}
+
+fn inner_type<'a>(ty: &'a Type, name: &str) -> Option<&'a Type> {
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last()?,
+        _ => return None,
+    };
+    if segment.ident != name {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first()? {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 #[proc_macro_derive(EventId)]
 pub fn derive_event_id(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -86,6 +275,21 @@ pub fn derive_event_id(input: TokenStream) -> TokenStream {
     let name = &input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => {
+            return syn::Error::new_spanned(name, "EventId supports only enums")
+                .to_compile_error()
+                .into();
+        }
+    };
+    if let Some(variant) = variants.iter().find(|variant| !matches!(variant.fields, Fields::Unit)) {
+        return syn::Error::new_spanned(variant, "EventId supports only unit variants")
+            .to_compile_error()
+            .into();
+    }
+    let variants = variants.iter().map(|variant| &variant.ident);
+
     let expanded = quote! {
         impl #impl_generics Into<u8> for #name #ty_generics #where_clause {
             fn into(self) -> u8 {
@@ -93,15 +297,24 @@ pub fn derive_event_id(input: TokenStream) -> TokenStream {
             }
         }
 
-        impl #impl_generics From<u8> for #name #ty_generics #where_clause {
-            fn from(value: u8) -> Self {
-                unsafe { std::mem::transmute(value) }
+        impl #impl_generics std::convert::TryFrom<u8> for #name #ty_generics #where_clause {
+            type Error = u8;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                #(
+                    if value == #name::#variants as u8 {
+                        return Ok(#name::#variants);
+                    }
+                )*
+                Err(value)
             }
         }
 
-        impl #impl_generics From<&u8> for #name #ty_generics #where_clause {
-            fn from(value: &u8) -> Self {
-                Self::from(*value)
+        impl #impl_generics std::convert::TryFrom<&u8> for #name #ty_generics #where_clause {
+            type Error = u8;
+
+            fn try_from(value: &u8) -> Result<Self, Self::Error> {
+                <Self as std::convert::TryFrom<u8>>::try_from(*value)
             }
         }
     };
@@ -0,0 +1,361 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use proc_macro2::{Ident, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
+use syn::{braced, parenthesized, token, Attribute, Error, Expr, Pat, Path, Result, Token};
+
+pub struct View {
+    root: Node,
+}
+
+impl View {
+    pub fn expand(&self) -> TokenStream {
+        self.root.expand()
+    }
+}
+
+impl Parse for View {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let root = input.parse()?;
+        if !input.is_empty() {
+            return Err(input.error("Expected a single root widget"));
+        }
+        Ok(View { root })
+    }
+}
+
+enum Argument {
+    Positional(Expr),
+    Named(Ident, Expr),
+}
+
+impl Parse for Argument {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(syn::Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            Ok(Argument::Named(name, input.parse()?))
+        } else {
+            Ok(Argument::Positional(input.parse()?))
+        }
+    }
+}
+
+struct MethodCall {
+    method: Ident,
+    arguments: Punctuated<Expr, Token![,]>,
+}
+
+enum Content {
+    Empty,
+    Child(Box<Node>),
+    Children(Vec<Child>),
+}
+
+struct Node {
+    path: Path,
+    arguments: Punctuated<Argument, Token![,]>,
+    calls: Vec<MethodCall>,
+    content: Content,
+}
+
+impl Node {
+    fn is_constructor(&self) -> bool {
+        self.path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident.to_string().starts_with(char::is_lowercase))
+    }
+
+    fn expand(&self) -> TokenStream {
+        let span = self.path.span();
+        let path = &self.path;
+        let mut positional = Vec::new();
+        let mut named = Vec::new();
+        for argument in &self.arguments {
+            match argument {
+                Argument::Positional(expr) => positional.push(quote! { #expr }),
+                Argument::Named(name, expr) => {
+                    let setter = Ident::new(&format!("with_{}", name), name.span());
+                    named.push(quote_spanned! { name.span()=> .#setter(#expr) });
+                }
+            }
+        }
+        if let Content::Child(child) = &self.content {
+            positional.push(child.expand());
+        }
+        let constructor = if self.is_constructor() {
+            quote_spanned! { span=> #path(#(#positional),*) }
+        } else if positional.is_empty() {
+            quote_spanned! { span=> <#path>::default() }
+        } else {
+            quote_spanned! { span=> <#path>::new(#(#positional),*) }
+        };
+        let calls = self.calls.iter().map(|call| {
+            let method = &call.method;
+            let arguments = &call.arguments;
+            quote_spanned! { method.span()=> .#method(#arguments) }
+        });
+        let widget = quote! { #constructor #(#named)* #(#calls)* };
+        match &self.content {
+            Content::Children(children) => {
+                let children = children.iter().map(Child::expand);
+                quote! {
+                    {
+                        let mut __view_widget = #widget;
+                        #(#children)*
+                        __view_widget
+                    }
+                }
+            }
+            _ => widget,
+        }
+    }
+}
+
+impl Parse for Node {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let path = input.parse()?;
+        let arguments = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            content.parse_terminated(Argument::parse)?
+        } else {
+            Punctuated::new()
+        };
+        let mut calls = Vec::new();
+        while input.peek(Token![.]) {
+            input.parse::<Token![.]>()?;
+            let method = input.parse()?;
+            let content;
+            parenthesized!(content in input);
+            calls.push(MethodCall {
+                method,
+                arguments: content.parse_terminated(Expr::parse)?,
+            });
+        }
+        let content = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            Content::Child(Box::new(input.parse()?))
+        } else if input.peek(token::Brace) {
+            Content::Children(parse_children(input)?)
+        } else {
+            Content::Empty
+        };
+        Ok(Node {
+            path,
+            arguments,
+            calls,
+            content,
+        })
+    }
+}
+
+enum ChildKind {
+    Node(Node),
+    Expr(Expr),
+    If(ChildIf),
+    For(ChildFor),
+}
+
+struct Child {
+    flex: Option<Expr>,
+    kind: ChildKind,
+}
+
+impl Child {
+    fn ends_with_brace(&self) -> bool {
+        match &self.kind {
+            ChildKind::Node(node) => match &node.content {
+                Content::Children(_) => true,
+                _ => false,
+            },
+            _ => true,
+        }
+    }
+
+    fn expand(&self) -> TokenStream {
+        let (span, widget) = match &self.kind {
+            ChildKind::Node(node) => (node.path.span(), node.expand()),
+            ChildKind::Expr(expr) => (expr.span(), quote! { #expr }),
+            ChildKind::If(child_if) => return child_if.expand(),
+            ChildKind::For(child_for) => return child_for.expand(),
+        };
+        match &self.flex {
+            Some(flex) => quote_spanned! { span=>
+                __view_widget = __view_widget.with_flex_child(#flex, #widget);
+            },
+            None => quote_spanned! { span=>
+                __view_widget = __view_widget.with_child(#widget);
+            },
+        }
+    }
+}
+
+impl Parse for Child {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut flex = None;
+        for attr in input.call(Attribute::parse_outer)? {
+            if !attr.path.is_ident("flex") {
+                return Err(Error::new_spanned(attr, "Unknown view attribute, expected #[flex(..)]"));
+            }
+            flex = Some((attr.parse_args()?, attr));
+        }
+        let kind = if input.peek(Token![if]) {
+            ChildKind::If(input.parse()?)
+        } else if input.peek(Token![for]) {
+            ChildKind::For(input.parse()?)
+        } else if input.peek(token::Brace) {
+            let content;
+            braced!(content in input);
+            let expr = content.parse()?;
+            if !content.is_empty() {
+                return Err(content.error("Expected a single widget expression"));
+            }
+            ChildKind::Expr(expr)
+        } else {
+            ChildKind::Node(input.parse()?)
+        };
+        match (flex, &kind) {
+            (Some((_, attr)), ChildKind::If(_)) | (Some((_, attr)), ChildKind::For(_)) => {
+                Err(Error::new_spanned(attr, "#[flex(..)] must be applied to a widget"))
+            }
+            (flex, _) => Ok(Child {
+                flex: flex.map(|(flex, _)| flex),
+                kind,
+            }),
+        }
+    }
+}
+
+struct ChildIf {
+    if_token: Token![if],
+    condition: Expr,
+    then_branch: Vec<Child>,
+    else_branch: Option<ChildElse>,
+}
+
+enum ChildElse {
+    If(Box<ChildIf>),
+    Children(Vec<Child>),
+}
+
+impl ChildIf {
+    fn expand(&self) -> TokenStream {
+        let span = self.if_token.span;
+        let condition = &self.condition;
+        let then_branch = self.then_branch.iter().map(Child::expand);
+        let else_branch = match &self.else_branch {
+            Some(ChildElse::If(child_if)) => {
+                let child_if = child_if.expand();
+                quote_spanned! { span=> else #child_if }
+            }
+            Some(ChildElse::Children(children)) => {
+                let children = children.iter().map(Child::expand);
+                quote_spanned! { span=> else { #(#children)* } }
+            }
+            None => quote! {},
+        };
+        quote_spanned! { span=>
+            if #condition {
+                #(#then_branch)*
+            } #else_branch
+        }
+    }
+}
+
+impl Parse for ChildIf {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let if_token = input.parse()?;
+        let condition = input.call(Expr::parse_without_eager_brace)?;
+        let then_branch = parse_children(input)?;
+        let else_branch = if input.peek(Token![else]) {
+            input.parse::<Token![else]>()?;
+            if input.peek(Token![if]) {
+                Some(ChildElse::If(Box::new(input.parse()?)))
+            } else {
+                Some(ChildElse::Children(parse_children(input)?))
+            }
+        } else {
+            None
+        };
+        Ok(ChildIf {
+            if_token,
+            condition,
+            then_branch,
+            else_branch,
+        })
+    }
+}
+
+struct ChildFor {
+    for_token: Token![for],
+    pat: Pat,
+    expr: Expr,
+    body: Vec<Child>,
+}
+
+impl ChildFor {
+    fn expand(&self) -> TokenStream {
+        let span = self.for_token.span;
+        let pat = &self.pat;
+        let expr = &self.expr;
+        let body = self.body.iter().map(Child::expand);
+        quote_spanned! { span=>
+            for #pat in #expr {
+                #(#body)*
+            }
+        }
+    }
+}
+
+impl Parse for ChildFor {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let for_token = input.parse()?;
+        let pat = input.parse()?;
+        input.parse::<Token![in]>()?;
+        let expr = input.call(Expr::parse_without_eager_brace)?;
+        let body = parse_children(input)?;
+        Ok(ChildFor {
+            for_token,
+            pat,
+            expr,
+            body,
+        })
+    }
+}
+
+fn parse_children(input: ParseStream) -> Result<Vec<Child>> {
+    let content;
+    braced!(content in input);
+    let mut children = Vec::new();
+    while !content.is_empty() {
+        let child: Child = content.parse()?;
+        if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+        } else if !content.is_empty() && !child.ends_with_brace() {
+            return Err(content.error("Expected `,` between widgets"));
+        }
+        children.push(child);
+    }
+    Ok(children)
+}
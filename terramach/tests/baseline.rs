@@ -0,0 +1,46 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach::testing::WidgetTester;
+use terramach::widgets::{Align, CrossAxisAlignment, Row, Text, TextStyle};
+
+#[test]
+fn row_aligns_children_on_baseline() {
+    let row = Row::new(None, CrossAxisAlignment::Baseline)
+        .with_child(Text::new("Small", TextStyle::builder().with_font_size(12.0)))
+        .with_child(Text::new("Large", TextStyle::builder().with_font_size(32.0)));
+    let mut tester = WidgetTester::new(Align::new(None, row)).with_size((400.0, 200.0));
+    tester.pump();
+
+    let row = tester.find_by_type::<Row>()[0];
+    let small = tester.find_by_text("Small")[0];
+    let large = tester.find_by_text("Large")[0];
+    let small_baseline = tester.baseline(small).unwrap();
+    let large_baseline = tester.baseline(large).unwrap();
+    assert!(large_baseline > small_baseline);
+
+    let row_bounds = tester.bounds(row).unwrap();
+    let row_baseline = tester.baseline(row).unwrap();
+    assert!((row_baseline - large_baseline).abs() < 0.01);
+
+    let small_bounds = tester.bounds(small).unwrap();
+    let large_bounds = tester.bounds(large).unwrap();
+    assert!(small_bounds.top > large_bounds.top);
+    assert!((small_bounds.top + small_baseline - (row_bounds.top + row_baseline)).abs() < 0.01);
+    assert!((large_bounds.top + large_baseline - (row_bounds.top + row_baseline)).abs() < 0.01);
+}
@@ -0,0 +1,113 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+use terramach::testing::WidgetTester;
+use terramach::*;
+
+const TICK: usize = 1;
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Ticker {
+    ticks: Rc<RefCell<usize>>,
+}
+
+impl Widget for Ticker {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        context.schedule_timer(TICK, Timer::new(Duration::from_millis(100), Duration::from_millis(50)));
+    }
+
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Event::Timer(TICK) = event.get() {
+            *self.ticks.borrow_mut() += 1;
+        }
+    }
+}
+
+#[test]
+fn timer_fires_when_manual_clock_advances() {
+    let clock = ManualClock::new();
+    let mut timers = Timers::new(clock.shared());
+    let id = timers.add(Timer::new(Duration::from_millis(100), None));
+
+    assert_eq!(timers.fire(), None);
+    clock.advance(Duration::from_millis(99));
+    assert_eq!(timers.fire(), None);
+    assert_eq!(timers.next_fire_time(), Some(Duration::from_millis(1)));
+
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(timers.fire(), Some(vec![id]));
+    assert!(timers.is_empty());
+}
+
+#[test]
+fn repeated_timer_fires_on_every_interval() {
+    let clock = ManualClock::new();
+    let mut timers = Timers::new(clock.shared());
+    let id = timers.add(Timer::new(Duration::from_millis(10), Duration::from_millis(20)));
+
+    clock.advance(Duration::from_millis(10));
+    assert_eq!(timers.fire(), Some(vec![id]));
+    clock.advance(Duration::from_millis(19));
+    assert_eq!(timers.fire(), None);
+    clock.advance(Duration::from_millis(1));
+    assert_eq!(timers.fire(), Some(vec![id]));
+    assert!(!timers.is_empty());
+}
+
+#[test]
+fn duration_driver_steps_with_manual_clock() {
+    let clock = ManualClock::new();
+    let mut animation: Animation<f32> = Duration::from_millis(200).animate(100.0);
+    animation.start(&clock);
+    assert!(animation.is_animating());
+
+    assert!(animation.advance(&clock));
+    assert_eq!(animation.value(), 0.0);
+
+    clock.advance(Duration::from_millis(50));
+    animation.advance(&clock);
+    assert_eq!(animation.value(), 25.0);
+
+    clock.advance(Duration::from_millis(150));
+    animation.advance(&clock);
+    assert_eq!(animation.value(), 100.0);
+
+    clock.advance(Duration::from_millis(16));
+    assert!(!animation.advance(&clock));
+    assert_eq!(animation.value(), 100.0);
+}
+
+#[test]
+fn widget_timer_fires_on_tester_clock() {
+    let ticks = Rc::new(RefCell::new(0));
+    let mut tester = WidgetTester::new(Ticker { ticks: ticks.clone() });
+    tester.pump();
+
+    tester.pump_for(Duration::from_millis(90));
+    assert_eq!(*ticks.borrow(), 0);
+
+    tester.pump_for(Duration::from_millis(10));
+    assert_eq!(*ticks.borrow(), 1);
+
+    tester.pump_for(Duration::from_millis(110));
+    assert_eq!(*ticks.borrow(), 3);
+}
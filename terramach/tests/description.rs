@@ -0,0 +1,104 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach::description::WidgetRegistry;
+use terramach::testing::WidgetTester;
+use terramach::widgets::{Column, Padding};
+
+const VALID: &str = r#"
+(
+    type: "Column",
+    children: [
+        (type: "Text", properties: {"text": "Hello"}),
+        (
+            type: "Padding",
+            properties: {"all": 8.0},
+            child: Some((type: "Text", properties: {"text": "Padded"})),
+        ),
+    ],
+)
+"#;
+
+#[test]
+fn loads_valid_ron() {
+    let widget = WidgetRegistry::new().load_ron(VALID).unwrap();
+    let mut tester = WidgetTester::new(widget);
+    tester.pump();
+    assert_eq!(tester.find_by_type::<Column>().len(), 1);
+    assert_eq!(tester.find_by_type::<Padding>().len(), 1);
+    assert_eq!(tester.find_by_text("Hello").len(), 1);
+    assert_eq!(tester.find_by_text("Padded").len(), 1);
+}
+
+#[test]
+fn unknown_widget_reports_path() {
+    let ron = r#"
+(
+    type: "Column",
+    children: [
+        (type: "Text", properties: {"text": "Hello"}),
+        (type: "Bogus"),
+    ],
+)
+"#;
+    let error = WidgetRegistry::new().load_ron(ron).err().unwrap();
+    assert_eq!(error.path(), "Column.children[1](Bogus)");
+    assert_eq!(error.message(), "Unknown widget type `Bogus`");
+}
+
+#[test]
+fn bad_property_reports_path() {
+    let ron = r#"
+(
+    type: "Column",
+    children: [
+        (type: "Text", properties: {"text": "Hello", "font_size": "large"}),
+    ],
+)
+"#;
+    let error = WidgetRegistry::new().load_ron(ron).err().unwrap();
+    assert_eq!(error.path(), "Column.children[0](Text)");
+    assert_eq!(error.message(), "Property `font_size` must be a number");
+}
+
+#[test]
+fn bad_field_reports_line_and_column() {
+    let ron = "(\n    type: \"Column\",\n    children: 5,\n)";
+    let error = WidgetRegistry::new().load_ron(ron).err().unwrap();
+    assert!(error.message().starts_with("Invalid RON"));
+    assert_eq!(error.line(), Some(3));
+    assert_eq!(error.column(), Some(15));
+}
+
+#[test]
+fn ron_syntax_error_reports_line_and_column() {
+    let ron = "(\n    type: \"Text\"\n    properties: {},\n)";
+    let error = WidgetRegistry::new().load_ron(ron).err().unwrap();
+    assert!(error.message().starts_with("Invalid RON"));
+    assert_eq!(error.line(), Some(3));
+    assert_eq!(error.column(), Some(5));
+}
+
+#[test]
+fn json_syntax_error_reports_line_and_column() {
+    let json = "{\n  \"type\": \"Text\",\n  \"properties\": {\"text\": }\n}";
+    let error = WidgetRegistry::new().load_json(json).err().unwrap();
+    assert!(error.message().starts_with("Invalid JSON"));
+    assert_eq!(error.line(), Some(3));
+    assert_eq!(error.column(), Some(26));
+}
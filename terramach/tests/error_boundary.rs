@@ -0,0 +1,64 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use terramach::testing::WidgetTester;
+use terramach::widgets::ErrorBoundary;
+use terramach::*;
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Fuse {
+    blown: Rc<Cell<bool>>,
+}
+
+impl Widget for Fuse {
+    fn build(&self, _: &mut WidgetContext, _: &mut BuildContext) {
+        if self.blown.get() {
+            panic!("fuse blown");
+        }
+    }
+
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Event::Key(_) = event.get() {
+            self.blown.set(true);
+            event.mark_need_build();
+        }
+    }
+
+    fn hit_test(&self, _: &WidgetContext, hit_test: &mut HitTestContext) -> bool {
+        hit_test.become_responder()
+    }
+}
+
+#[test]
+fn rebuild_panic_below_boundary_shows_fallback() {
+    let blown = Rc::new(Cell::new(false));
+    let mut tester = WidgetTester::new(ErrorBoundary::new(Fuse { blown: blown.clone() }));
+    tester.pump();
+    assert_eq!(tester.find_by_type::<Fuse>().len(), 1);
+
+    tester.tap((10.0, 10.0));
+    tester.send_key(HitKey::new('a', Key::A as KeyScanCode, KeyAction::Press, KeyModifiers::default()));
+    tester.pump();
+
+    assert!(blown.get());
+    assert!(tester.find_by_type::<Fuse>().is_empty());
+    assert_eq!(tester.find_by_text("Build failed: fuse blown").len(), 1);
+}
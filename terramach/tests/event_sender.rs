@@ -0,0 +1,146 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use terramach::testing::WidgetTester;
+use terramach::*;
+
+#[derive(Clone, Default, PartialWidget)]
+struct Receiver {
+    sender: Rc<RefCell<Option<WidgetEventSender>>>,
+    messages: Rc<RefCell<Vec<String>>>,
+}
+
+impl PartialEq for Receiver {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.messages, &other.messages)
+    }
+}
+
+impl Receiver {
+    fn sender(&self) -> WidgetEventSender {
+        self.sender.borrow().clone().unwrap()
+    }
+}
+
+impl Widget for Receiver {
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        *self.sender.borrow_mut() = Some(build.event_sender());
+    }
+
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Some(message) = event.custom::<String>() {
+            self.messages.borrow_mut().push(message.clone());
+        }
+    }
+}
+
+#[derive(Clone, PartialWidget)]
+struct Host {
+    shown: Rc<Cell<bool>>,
+    handle: Rc<RefCell<Option<WidgetHandle>>>,
+    receiver: Receiver,
+}
+
+impl PartialEq for Host {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.shown, &other.shown)
+    }
+}
+
+impl Widget for Host {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        *self.handle.borrow_mut() = Some(context.handle());
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        if self.shown.get() {
+            build.add_child(self.receiver.clone());
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn event_from_another_thread_wakes_run_loop() {
+    use terramach::platform::RunLoop;
+
+    let mut run_loop = RunLoop::new();
+    let receiver = Receiver::default();
+    let tester = Rc::new(RefCell::new(WidgetTester::new(receiver.clone()).with_run_loop(run_loop.share())));
+    tester.borrow_mut().pump();
+
+    let sender = receiver.sender();
+    let messages = receiver.messages.clone();
+    let mut shared = run_loop.share();
+    let mut started = false;
+    run_loop.add_observer(move || {
+        if !started {
+            started = true;
+            // fail instead of hanging when the sender never wakes the run loop
+            shared.set_next_wakeup_in(Duration::from_secs(5));
+            let sender = sender.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                sender.send_custom("from worker".to_string());
+            });
+            return;
+        }
+        tester.borrow_mut().pump();
+        if !messages.borrow().is_empty() {
+            shared.stop();
+        }
+    });
+
+    let start = Instant::now();
+    run_loop.run();
+
+    assert_eq!(*receiver.messages.borrow(), vec!["from worker".to_string()]);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
+
+#[test]
+fn send_after_unmount_is_dropped() {
+    let shown = Rc::new(Cell::new(true));
+    let handle = Rc::new(RefCell::new(None));
+    let receiver = Receiver::default();
+    let mut tester = WidgetTester::new(Host {
+        shown: shown.clone(),
+        handle: handle.clone(),
+        receiver: receiver.clone(),
+    });
+    tester.pump();
+    let sender = receiver.sender();
+    assert!(!sender.is_closed());
+
+    shown.set(false);
+    handle.borrow().as_ref().unwrap().mark_need_build();
+    tester.pump();
+    tester.pump();
+    assert!(tester.find_by_type::<Receiver>().is_empty());
+    assert!(sender.is_closed());
+
+    let worker = thread::spawn(move || sender.send_custom("too late".to_string()));
+    assert!(!worker.join().unwrap());
+    tester.pump();
+    assert!(receiver.messages.borrow().is_empty());
+}
@@ -0,0 +1,111 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::RefCell;
+use std::convert::TryFrom;
+use std::rc::Rc;
+
+use terramach::testing::WidgetTester;
+use terramach::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, EventId)]
+enum Action {
+    Open,
+    Close,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Payload {
+    name: String,
+    count: usize,
+}
+
+#[derive(Clone, PartialWidget)]
+struct Receiver {
+    sender: Rc<RefCell<Option<WidgetEventSender>>>,
+    payloads: Rc<RefCell<Vec<Payload>>>,
+    mismatches: Rc<RefCell<usize>>,
+}
+
+impl PartialEq for Receiver {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.payloads, &other.payloads)
+    }
+}
+
+impl Widget for Receiver {
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        *self.sender.borrow_mut() = Some(build.event_sender());
+    }
+
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Some(payload) = event.custom::<Payload>() {
+            self.payloads.borrow_mut().push(payload.clone());
+            if event.custom::<String>().is_none() {
+                *self.mismatches.borrow_mut() += 1;
+            }
+        }
+    }
+}
+
+#[test]
+fn event_id_converts_to_and_from_u8() {
+    let close: u8 = Action::Close.into();
+    assert_eq!(close, 1);
+    assert_eq!(Action::try_from(0u8), Ok(Action::Open));
+    assert_eq!(Action::try_from(&1u8), Ok(Action::Close));
+}
+
+#[test]
+fn out_of_range_event_id_is_returned_as_error() {
+    assert_eq!(Action::try_from(2u8), Err(2));
+    assert_eq!(Action::try_from(&255u8), Err(255));
+}
+
+#[test]
+fn custom_event_payload_round_trips() {
+    let payload = Payload {
+        name: "ping".to_string(),
+        count: 3,
+    };
+    let event = Event::Custom(CustomEvent::new(payload.clone()));
+    assert_eq!(event.custom::<Payload>(), Some(&payload));
+    assert_eq!(event.custom::<String>(), None);
+    assert_eq!(Event::Enter.custom::<Payload>(), None);
+}
+
+#[test]
+fn custom_event_reaches_widget() {
+    let receiver = Receiver {
+        sender: Default::default(),
+        payloads: Default::default(),
+        mismatches: Default::default(),
+    };
+    let mut tester = WidgetTester::new(receiver.clone());
+    tester.pump();
+
+    let payload = Payload {
+        name: "ping".to_string(),
+        count: 3,
+    };
+    let sender = receiver.sender.borrow().clone().unwrap();
+    assert!(sender.send_custom(payload.clone()));
+    tester.pump();
+    assert_eq!(*receiver.payloads.borrow(), vec![payload]);
+    assert_eq!(*receiver.mismatches.borrow(), 1);
+}
@@ -0,0 +1,110 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use terramach::graphics::{Color, Point};
+use terramach::testing::WidgetTester;
+use terramach::widgets::{Decoration, Gesture};
+use terramach::*;
+
+fn surface() -> Decoration {
+    Decoration::new_empty(Color::WHITE, None)
+}
+
+#[test]
+fn tap_invokes_tap_callback() {
+    let taps = Rc::new(Cell::new(0));
+    let counter = taps.clone();
+    let mut tester = WidgetTester::new(
+        Gesture::new_child(surface()).on_tap(move || counter.set(counter.get() + 1)),
+    );
+    tester.pump();
+
+    tester.tap((10.0, 10.0));
+    assert_eq!(taps.get(), 1);
+
+    tester.tap((100.0, 100.0));
+    assert_eq!(taps.get(), 2);
+}
+
+#[test]
+fn drag_reports_pan_from_start_to_end() {
+    let locations = Rc::new(RefCell::new(Vec::new()));
+    let ended = Rc::new(Cell::new(false));
+    let (start_locations, update_locations, end) = (locations.clone(), locations.clone(), ended.clone());
+    let mut tester = WidgetTester::new(
+        Gesture::new_child(surface())
+            .on_pan_start(move |location| start_locations.borrow_mut().push(location))
+            .on_pan_update(move |location| update_locations.borrow_mut().push(location))
+            .on_pan_end(move || end.set(true)),
+    );
+    tester.pump();
+
+    tester.drag((10.0, 10.0), (210.0, 10.0));
+
+    let locations = locations.borrow();
+    assert!(!locations.is_empty());
+    assert_eq!(*locations.last().unwrap(), Point::new(210.0, 10.0));
+    assert!(ended.get());
+}
+
+#[test]
+fn long_press_fires_after_the_press_delay() {
+    let pressed = Rc::new(Cell::new(false));
+    let long_press = pressed.clone();
+    let mut tester = WidgetTester::new(
+        Gesture::new_child(surface()).on_long_press(move || long_press.set(true)),
+    );
+    tester.pump();
+
+    tester.emit_event(AppEvent::TouchBegin(Touch::new(1, (10.0, 10.0))));
+    tester.pump_for(Duration::from_millis(300));
+    assert!(!pressed.get());
+
+    tester.pump_for(Duration::from_millis(300));
+    assert!(pressed.get());
+    tester.emit_event(AppEvent::TouchEnd(Touch::new(1, (10.0, 10.0))));
+}
+
+#[test]
+fn callback_marks_owner_for_rebuild() {
+    let builds = Rc::new(Cell::new(0));
+    let mut tester = WidgetTester::new(Counter { builds: builds.clone() });
+    tester.pump();
+    assert_eq!(builds.get(), 1);
+
+    tester.tap((10.0, 10.0));
+    tester.pump();
+    assert_eq!(builds.get(), 2);
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Counter {
+    builds: Rc<Cell<usize>>,
+}
+
+impl Widget for Counter {
+    fn build(&self, context: &mut WidgetContext, build: &mut BuildContext) {
+        self.builds.set(self.builds.get() + 1);
+        let handle = context.handle();
+        build.add_child(Gesture::new_child(surface()).on_tap(move || handle.mark_need_build()));
+    }
+}
@@ -0,0 +1,85 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use terramach::graphics::Color;
+use terramach::testing::{Golden, GoldenError};
+use terramach::widgets::{Decoration, Padding, Text};
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("goldens")
+        .join(name)
+}
+
+#[test]
+fn padded_decoration_matches_golden() {
+    let result = Golden::new((8.0, 8.0)).compare(
+        Padding::new_all(2.0, Decoration::new_empty(Color::RED, None)),
+        golden_path("padded_decoration.png"),
+    );
+    if let Err(error) = result {
+        panic!("{}", error);
+    }
+}
+
+#[test]
+fn missing_golden_fails() {
+    if env::var_os("TERRAMACH_UPDATE_GOLDENS").is_some() {
+        return;
+    }
+    let golden = golden_path("missing.png");
+    let result = Golden::new((8.0, 8.0)).compare(
+        Decoration::new_empty(Color::RED, None),
+        &golden,
+    );
+    match result {
+        Err(GoldenError::Missing(path)) => assert_eq!(path, golden),
+        _ => panic!("A missing golden must fail the comparison"),
+    }
+    assert!(!golden.exists());
+}
+
+#[test]
+fn text_renders_identically_with_bundled_font() {
+    if env::var_os("TERRAMACH_UPDATE_GOLDENS").is_some() {
+        return;
+    }
+    let golden = Golden::new((48.0, 24.0));
+    let path = env::temp_dir().join(format!("terramach-text-{}.png", process::id()));
+    fs::write(&path, golden.render_png(Text::new_text("Hg")).unwrap()).unwrap();
+
+    let same = golden.compare(Text::new_text("Hg"), &path);
+    let empty = golden.compare(Text::new_text(""), &path);
+    for extension in &["png", "diff.png", "actual.png"] {
+        let _ = fs::remove_file(path.with_extension(extension));
+    }
+
+    if let Err(error) = same {
+        panic!("{}", error);
+    }
+    match empty {
+        Err(GoldenError::PixelMismatch { pixels, .. }) => assert!(pixels > 0),
+        _ => panic!("Text must draw glyphs with the bundled font"),
+    }
+}
@@ -0,0 +1,121 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use terramach::testing::WidgetTester;
+use terramach::widgets::Column;
+use terramach::*;
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Theme {
+    value: u32,
+    child: BoxedWidget,
+}
+
+impl Widget for Theme {
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(self.child.clone());
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Dependent {
+    builds: Rc<Cell<usize>>,
+}
+
+impl Widget for Dependent {
+    fn mount(&self, context: &mut WidgetContext, mount: &mut MountContext) {
+        context.set_state(mount.inherited_widget::<Theme>().map_or(0, |theme| theme.value));
+    }
+
+    fn update(&self, context: &mut WidgetContext, update: &mut UpdateContext) {
+        context.set_state(update.inherited_widget::<Theme>().map_or(0, |theme| theme.value));
+    }
+
+    fn build(&self, _: &mut WidgetContext, _: &mut BuildContext) {
+        self.builds.set(self.builds.get() + 1);
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Bystander {
+    builds: Rc<Cell<usize>>,
+}
+
+impl Widget for Bystander {
+    fn build(&self, _: &mut WidgetContext, _: &mut BuildContext) {
+        self.builds.set(self.builds.get() + 1);
+    }
+}
+
+#[derive(Clone, PartialWidget)]
+struct Host {
+    value: Rc<Cell<u32>>,
+    handle: Rc<RefCell<Option<WidgetHandle>>>,
+    dependent: Rc<Cell<usize>>,
+    bystander: Rc<Cell<usize>>,
+}
+
+impl PartialEq for Host {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.value, &other.value)
+    }
+}
+
+impl Widget for Host {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        *self.handle.borrow_mut() = Some(context.handle());
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(Theme {
+            value: self.value.get(),
+            child: Column::default()
+                .with_child(Bystander { builds: self.bystander.clone() })
+                .with_child(Dependent { builds: self.dependent.clone() })
+                .into(),
+        });
+    }
+}
+
+#[test]
+fn inherited_change_rebuilds_only_dependents() {
+    let host = Host {
+        value: Rc::new(Cell::new(1)),
+        handle: Rc::new(RefCell::new(None)),
+        dependent: Rc::new(Cell::new(0)),
+        bystander: Rc::new(Cell::new(0)),
+    };
+    let mut tester = WidgetTester::new(host.clone());
+    tester.pump();
+    let dependent = tester.find_by_type::<Dependent>()[0];
+    assert_eq!(tester.context(dependent).unwrap().state::<u32>(), Some(&1));
+    assert_eq!((host.dependent.get(), host.bystander.get()), (1, 1));
+
+    host.value.set(2);
+    host.handle.borrow().as_ref().unwrap().mark_need_build();
+    tester.pump();
+    tester.pump();
+
+    assert_eq!(tester.find_by_type::<Dependent>(), vec![dependent]);
+    assert_eq!(tester.context(dependent).unwrap().state::<u32>(), Some(&2));
+    assert_eq!(host.dependent.get(), 2);
+    assert_eq!(host.bystander.get(), 1);
+}
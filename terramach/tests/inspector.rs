@@ -0,0 +1,62 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use serde_json::Value;
+
+use terramach::testing::WidgetTester;
+use terramach::widgets::{Column, Padding, Text};
+
+fn find_widget<'a>(node: &'a Value, type_name: &str) -> Option<&'a Value> {
+    if node["type"].as_str().map_or(false, |name| name.ends_with(type_name)) {
+        return Some(node);
+    }
+    node["children"]
+        .as_array()?
+        .iter()
+        .find_map(|child| find_widget(child, type_name))
+}
+
+fn child_types(node: &Value) -> Vec<String> {
+    node["children"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|child| child["type"].as_str().unwrap().rsplit("::").next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn dump_contains_widget_hierarchy() {
+    let mut tester = WidgetTester::new(
+        Column::default()
+            .with_child(Text::new_text("first"))
+            .with_child(Padding::new_all(4.0, Text::new_text("second"))),
+    );
+    tester.pump();
+
+    let dump = tester.dump();
+    let inspected: Value = serde_json::from_str(&dump).unwrap();
+    assert_eq!(inspected, tester.inspect());
+
+    let column = find_widget(&inspected["widgets"], "Column").unwrap();
+    assert_eq!(child_types(column), vec!["Text", "Padding"]);
+    let padding = find_widget(column, "Padding").unwrap();
+    assert_eq!(child_types(padding), vec!["Text"]);
+    assert!(padding["size"]["width"].as_f64().unwrap() > 0.0);
+    assert!(inspected["layers"].is_array());
+}
@@ -0,0 +1,56 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach::testing::WidgetTester;
+use terramach::widgets::{Align, IntrinsicHeight, IntrinsicWidth, Row, Text, TextStyle};
+use terramach::*;
+
+fn texts() -> Row {
+    Row::default()
+        .with_child(Text::new("Small", TextStyle::builder().with_font_size(12.0)))
+        .with_child(Text::new("Large", TextStyle::builder().with_font_size(32.0)))
+        .with_child(Text::new("Medium", TextStyle::builder().with_font_size(20.0)))
+}
+
+fn text_bounds(tester: &WidgetTester) -> Vec<graphics::Rect> {
+    ["Small", "Large", "Medium"]
+        .iter()
+        .map(|text| tester.bounds(tester.find_by_text(text)[0]).unwrap())
+        .collect()
+}
+
+#[test]
+fn intrinsic_width_sizes_row_to_sum_of_children() {
+    let mut tester = WidgetTester::new(Align::new(None, IntrinsicWidth::new(texts()))).with_size((400.0, 200.0));
+    tester.pump();
+    let row = tester.find_by_type::<Row>()[0];
+    let width: f32 = text_bounds(&tester).iter().map(|bounds| bounds.width()).sum();
+    assert!(width > 0.0);
+    assert!((tester.bounds(row).unwrap().width() - width).abs() < 0.01);
+}
+
+#[test]
+fn intrinsic_height_sizes_row_to_max_of_children() {
+    let mut tester = WidgetTester::new(Align::new(None, IntrinsicHeight::new(texts()))).with_size((400.0, 200.0));
+    tester.pump();
+    let row = tester.find_by_type::<Row>()[0];
+    let bounds = text_bounds(&tester);
+    let height = bounds.iter().map(|bounds| bounds.height()).fold(0.0, f32::max);
+    assert!(height > bounds[0].height());
+    assert!((tester.bounds(row).unwrap().height() - height).abs() < 0.01);
+}
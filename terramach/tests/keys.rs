@@ -0,0 +1,161 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use terramach::testing::WidgetTester;
+use terramach::widgets::Column;
+use terramach::*;
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Item {
+    label: &'static str,
+    key: Option<WidgetKey>,
+    mounts: Rc<Cell<usize>>,
+}
+
+impl Widget for Item {
+    fn key(&self) -> Option<&WidgetKey> {
+        self.key.as_ref()
+    }
+
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        self.mounts.set(self.mounts.get() + 1);
+        context.set_state(self.mounts.get());
+    }
+}
+
+#[derive(Clone, PartialWidget)]
+struct List {
+    items: Rc<RefCell<Vec<Item>>>,
+    handle: Rc<RefCell<Option<WidgetHandle>>>,
+}
+
+impl PartialEq for List {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.items, &other.items)
+    }
+}
+
+impl Widget for List {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        *self.handle.borrow_mut() = Some(context.handle());
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        let mut column = Column::default();
+        for item in self.items.borrow().iter() {
+            column = column.with_child(item.clone());
+        }
+        build.add_child(column);
+    }
+}
+
+struct Fixture {
+    tester: WidgetTester,
+    items: Rc<RefCell<Vec<Item>>>,
+    handle: Rc<RefCell<Option<WidgetHandle>>>,
+    mounts: Rc<Cell<usize>>,
+}
+
+impl Fixture {
+    fn new(items: &[(&'static str, bool)]) -> Self {
+        let mounts = Rc::new(Cell::new(0));
+        let items = Rc::new(RefCell::new(Fixture::items(items, &mounts)));
+        let handle = Rc::new(RefCell::new(None));
+        let mut tester = WidgetTester::new(List {
+            items: items.clone(),
+            handle: handle.clone(),
+        });
+        tester.pump();
+        Fixture {
+            tester,
+            items,
+            handle,
+            mounts,
+        }
+    }
+
+    fn items(items: &[(&'static str, bool)], mounts: &Rc<Cell<usize>>) -> Vec<Item> {
+        items
+            .iter()
+            .map(|(label, keyed)| Item {
+                label: *label,
+                key: if *keyed { Some(WidgetKey::from(*label)) } else { None },
+                mounts: mounts.clone(),
+            })
+            .collect()
+    }
+
+    fn rebuild(&mut self, items: &[(&'static str, bool)]) {
+        *self.items.borrow_mut() = Fixture::items(items, &self.mounts);
+        self.handle.borrow().as_ref().unwrap().mark_need_build();
+        self.tester.pump();
+        self.tester.pump();
+    }
+
+    fn item(&self, label: &str) -> Option<(Id, usize)> {
+        let id = self
+            .tester
+            .find_by_type::<Item>()
+            .into_iter()
+            .find(|id| self.tester.widget::<Item>(*id).unwrap().label == label)?;
+        let state = *self.tester.context(id)?.state::<usize>()?;
+        Some((id, state))
+    }
+}
+
+#[test]
+fn reordered_keyed_children_keep_ids_and_state() {
+    let mut fixture = Fixture::new(&[("a", true), ("b", true), ("c", true)]);
+    let before = ["a", "b", "c"].iter().map(|label| fixture.item(label).unwrap()).collect::<Vec<_>>();
+    assert_eq!(fixture.mounts.get(), 3);
+
+    fixture.rebuild(&[("c", true), ("a", true), ("b", true)]);
+
+    let after = ["a", "b", "c"].iter().map(|label| fixture.item(label).unwrap()).collect::<Vec<_>>();
+    assert_eq!(before, after);
+    assert_eq!(fixture.mounts.get(), 3);
+}
+
+#[test]
+fn unkeyed_child_is_not_matched_to_keyed_slot() {
+    let mut fixture = Fixture::new(&[("a", true)]);
+    let (keyed_id, keyed_state) = fixture.item("a").unwrap();
+
+    fixture.rebuild(&[("b", false), ("a", true)]);
+
+    assert_eq!(fixture.item("a"), Some((keyed_id, keyed_state)));
+    let (unkeyed_id, unkeyed_state) = fixture.item("b").unwrap();
+    assert_ne!(unkeyed_id, keyed_id);
+    assert_eq!(unkeyed_state, 2);
+    assert_eq!(fixture.mounts.get(), 2);
+}
+
+#[test]
+fn keyed_slot_is_not_reused_by_unkeyed_child() {
+    let mut fixture = Fixture::new(&[("a", true)]);
+    let (keyed_id, _) = fixture.item("a").unwrap();
+
+    fixture.rebuild(&[("a", false)]);
+
+    let (unkeyed_id, unkeyed_state) = fixture.item("a").unwrap();
+    assert_ne!(unkeyed_id, keyed_id);
+    assert_eq!(unkeyed_state, 2);
+}
@@ -0,0 +1,65 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach::graphics::Size;
+use terramach::testing::WidgetTester;
+use terramach::widgets::Row;
+use terramach::*;
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Fixed {
+    width: f32,
+    height: f32,
+}
+
+impl Widget for Fixed {
+    fn layout(&self, _: &mut WidgetContext, _: &mut LayoutContext) -> Size {
+        Size::new(self.width, self.height)
+    }
+}
+
+fn fixed(width: f32, height: f32) -> Fixed {
+    Fixed { width, height }
+}
+
+#[test]
+fn row_with_wider_children_overflows() {
+    let mut tester = WidgetTester::new(Row::default().with_child(fixed(80.0, 20.0)).with_child(fixed(80.0, 20.0)))
+        .with_size((100.0, 50.0));
+    tester.pump();
+    let row = tester.find_by_type::<Row>()[0];
+    assert_eq!(tester.bounds(row).unwrap().width(), 100.0);
+    assert_eq!(tester.overflow(row), Some(Size::new(60.0, 0.0)));
+}
+
+#[test]
+fn row_with_fitting_children_does_not_overflow() {
+    let mut tester = WidgetTester::new(Row::default().with_child(fixed(40.0, 20.0)).with_child(fixed(40.0, 20.0)))
+        .with_size((100.0, 50.0));
+    tester.pump();
+    let row = tester.find_by_type::<Row>()[0];
+    assert_eq!(tester.overflow(row), Some(Size::new(0.0, 0.0)));
+}
+
+#[test]
+fn row_with_taller_children_overflows_in_both_directions() {
+    let mut tester = WidgetTester::new(Row::default().with_child(fixed(160.0, 80.0))).with_size((100.0, 50.0));
+    tester.pump();
+    let row = tester.find_by_type::<Row>()[0];
+    assert_eq!(tester.overflow(row), Some(Size::new(60.0, 30.0)));
+}
@@ -0,0 +1,105 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach::gpu::RasterCache;
+use terramach::graphics::{AlphaType, ColorType, Data, Image, ImageInfo, Size};
+
+const SCALE: (f32, f32) = (1.0, 1.0);
+
+fn image(width: i32, height: i32) -> Image {
+    let info = ImageInfo::new((width, height), ColorType::RGBA8888, AlphaType::Unpremul, None);
+    let row_bytes = info.min_row_bytes();
+    let pixels = vec![0; row_bytes * height as usize];
+    Image::from_raster_data(&info, Data::new_copy(&pixels), row_bytes).unwrap()
+}
+
+fn insert(cache: &mut RasterCache, id: usize, version: u64, width: i32, height: i32) {
+    let size = Size::new(width as f32, height as f32);
+    cache.insert(id, version, size, SCALE, image(width, height));
+}
+
+#[test]
+fn unchanged_boundary_hits_cache() {
+    let size = Size::new(16.0, 16.0);
+    let mut cache = RasterCache::new();
+    insert(&mut cache, 1, 1, 16, 16);
+    assert!(cache.image(1, 1, size, SCALE).is_some());
+    cache.sweep();
+    assert!(cache.image(1, 1, size, SCALE).is_some());
+    assert!(cache.image(1, 2, size, SCALE).is_none());
+    assert!(cache.image(1, 1, Size::new(8.0, 8.0), SCALE).is_none());
+    assert!(cache.image(1, 1, size, (2.0, 2.0)).is_none());
+}
+
+#[test]
+fn boundaries_not_drawn_in_frame_are_swept() {
+    let mut cache = RasterCache::new();
+    insert(&mut cache, 1, 1, 16, 16);
+    insert(&mut cache, 2, 1, 16, 16);
+    cache.sweep();
+    assert_eq!(cache.len(), 2);
+    assert!(cache.image(1, 1, Size::new(16.0, 16.0), SCALE).is_some());
+    cache.sweep();
+    assert_eq!(cache.len(), 1);
+    assert!(cache.image(2, 1, Size::new(16.0, 16.0), SCALE).is_none());
+}
+
+#[test]
+fn default_budget_is_64mb() {
+    let cache = RasterCache::new();
+    assert_eq!(cache.budget(), 64 * 1024 * 1024);
+    assert!(cache.fits(1, 4096, 4096));
+}
+
+#[test]
+fn entries_above_max_dimension_are_skipped() {
+    let cache = RasterCache::new();
+    assert!(!cache.fits(1, 4097, 16));
+    assert!(!cache.fits(1, 16, 4097));
+    let cache = RasterCache::new().with_budget(usize::max_value());
+    assert!(!cache.fits(1, 4097, 4097));
+}
+
+#[test]
+fn unused_entries_are_evicted_over_budget() {
+    let entry_bytes = 64 * 64 * 4;
+    let mut cache = RasterCache::new().with_budget(2 * entry_bytes);
+    insert(&mut cache, 1, 1, 64, 64);
+    insert(&mut cache, 2, 1, 64, 64);
+    cache.sweep();
+    assert_eq!(cache.used_bytes(), 2 * entry_bytes);
+
+    assert!(cache.image(1, 1, Size::new(64.0, 64.0), SCALE).is_some());
+    assert!(cache.fits(3, 64, 64));
+    insert(&mut cache, 3, 1, 64, 64);
+    assert_eq!(cache.len(), 2);
+    assert!(cache.used_bytes() <= cache.budget());
+    assert!(cache.image(1, 1, Size::new(64.0, 64.0), SCALE).is_some());
+    assert!(cache.image(2, 1, Size::new(64.0, 64.0), SCALE).is_none());
+    assert!(cache.image(3, 1, Size::new(64.0, 64.0), SCALE).is_some());
+}
+
+#[test]
+fn entries_drawn_in_frame_are_not_evicted() {
+    let entry_bytes = 64 * 64 * 4;
+    let mut cache = RasterCache::new().with_budget(2 * entry_bytes);
+    insert(&mut cache, 1, 1, 64, 64);
+    insert(&mut cache, 2, 1, 64, 64);
+    assert!(!cache.fits(3, 64, 64));
+    assert!(cache.fits(2, 64, 64));
+}
@@ -0,0 +1,90 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+#![cfg(target_os = "linux")]
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use terramach::platform::RunLoop;
+
+#[test]
+fn observers_run_until_stopped() {
+    let mut run_loop = RunLoop::new();
+    let runs = Rc::new(Cell::new(0));
+    let (first, second) = (runs.clone(), runs.clone());
+    let mut shared = run_loop.share();
+    run_loop.add_observer(move || {
+        first.set(first.get() + 1);
+        shared.stop();
+    });
+    run_loop.add_observer(move || second.set(second.get() + 1));
+
+    run_loop.run();
+
+    assert_eq!(runs.get(), 2);
+    assert!(!run_loop.is_running());
+}
+
+#[test]
+fn wakeup_from_another_thread_runs_observers_again() {
+    let mut run_loop = RunLoop::new();
+    let runs = Rc::new(Cell::new(0));
+    let counter = runs.clone();
+    let mut shared = run_loop.share();
+    run_loop.add_observer(move || {
+        counter.set(counter.get() + 1);
+        if counter.get() == 1 {
+            let mut wakeup = shared.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(10));
+                wakeup.wakeup();
+            });
+        } else {
+            shared.stop();
+        }
+    });
+
+    run_loop.run();
+
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn next_wakeup_runs_observers_after_the_delay() {
+    let mut run_loop = RunLoop::new();
+    let runs = Rc::new(Cell::new(0));
+    let counter = runs.clone();
+    let mut shared = run_loop.share();
+    run_loop.add_observer(move || {
+        counter.set(counter.get() + 1);
+        if counter.get() == 1 {
+            shared.set_next_wakeup_in(Duration::from_millis(20));
+        } else {
+            shared.stop();
+        }
+    });
+
+    let start = Instant::now();
+    run_loop.run();
+
+    assert_eq!(runs.get(), 2);
+    assert!(start.elapsed() >= Duration::from_millis(20));
+}
@@ -0,0 +1,86 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process;
+use std::time::Duration;
+
+use terramach::testing::WidgetTester;
+use terramach::widgets::Text;
+use terramach::*;
+
+type FileOutput = TaskOutput<Result<io::Result<String>, BlockingError>>;
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct FileText {
+    path: Option<PathBuf>,
+}
+
+impl Widget for FileText {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        context.set_state(String::from("Loading"));
+        let path = self.path.clone();
+        context.spawn(spawn_blocking(move || match path {
+            Some(path) => fs::read_to_string(path),
+            None => panic!("no file"),
+        }));
+    }
+
+    fn build(&self, context: &mut WidgetContext, build: &mut BuildContext) {
+        let text = context.state::<String>().cloned().unwrap_or_default();
+        build.add_child(Text::new_text(text));
+    }
+
+    fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
+        if let Some(output) = event.custom::<FileOutput>().and_then(|output| output.take()) {
+            let text = match output {
+                Ok(Ok(text)) => text,
+                Ok(Err(error)) => error.to_string(),
+                Err(error) => error.to_string(),
+            };
+            context.set_state(text);
+            event.mark_need_build();
+        }
+    }
+}
+
+#[test]
+fn blocking_file_read_updates_text() {
+    let path = std::env::temp_dir().join(format!("terramach-tasks-{}.txt", process::id()));
+    fs::write(&path, "Hello from disk").unwrap();
+
+    let mut tester = WidgetTester::new(FileText { path: Some(path.clone()) });
+    tester.pump();
+    assert_eq!(tester.find_by_text("Loading").len(), 1);
+
+    assert!(tester.pump_tasks(Duration::from_secs(5)));
+    fs::remove_file(&path).unwrap();
+    assert!(tester.find_by_text("Loading").is_empty());
+    assert_eq!(tester.find_by_text("Hello from disk").len(), 1);
+}
+
+#[test]
+fn blocking_panic_resolves_the_task() {
+    let mut tester = WidgetTester::new(FileText { path: None });
+    tester.pump();
+
+    assert!(tester.pump_tasks(Duration::from_secs(5)));
+    assert_eq!(tester.find_by_text("Blocking job panicked: no file").len(), 1);
+}
@@ -0,0 +1,193 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+use terramach::graphics::{Color, Point};
+use terramach::testing::WidgetTester;
+use terramach::widgets::{Column, Decoration, Gesture, Text};
+use terramach::*;
+
+const TAP: EventId = 1;
+const TIMEOUT: usize = 1;
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct TapCounter {
+    taps: Rc<Cell<usize>>,
+}
+
+impl Widget for TapCounter {
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(Gesture::new(
+            TAP,
+            build.event_emitter(),
+            TapGesture::default(),
+            None,
+            Decoration::new_empty(Color::WHITE, None),
+        ));
+    }
+
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Event::Tap(TAP) = event.get() {
+            self.taps.set(self.taps.get() + 1);
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct TouchRecorder {
+    locations: Rc<RefCell<Vec<Point>>>,
+    ended: Rc<Cell<bool>>,
+}
+
+impl Widget for TouchRecorder {
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        match event.get() {
+            Event::TouchBegin(touch) | Event::TouchUpdate(touch) => {
+                self.locations.borrow_mut().push(touch.location());
+            }
+            Event::TouchEnd(_) => self.ended.set(true),
+            _ => {}
+        }
+    }
+
+    fn hit_test(&self, _: &WidgetContext, hit_test: &mut HitTestContext) -> bool {
+        hit_test.become_responder()
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct KeyRecorder {
+    keys: Rc<RefCell<Vec<Key>>>,
+}
+
+impl Widget for KeyRecorder {
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Event::Key(key) = event.get() {
+            self.keys.borrow_mut().push(key.key());
+        }
+    }
+
+    fn hit_test(&self, _: &WidgetContext, hit_test: &mut HitTestContext) -> bool {
+        hit_test.become_responder()
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Timeout {
+    fired: Rc<Cell<bool>>,
+}
+
+impl Widget for Timeout {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        context.schedule_timer(TIMEOUT, Timer::new(Duration::from_millis(500), None));
+    }
+
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Event::Timer(TIMEOUT) = event.get() {
+            self.fired.set(true);
+        }
+    }
+}
+
+fn press(key: Key, character: char) -> HitKey {
+    HitKey::new(character, key as KeyScanCode, KeyAction::Press, KeyModifiers::default())
+}
+
+#[test]
+fn tap_emits_tap_event() {
+    let taps = Rc::new(Cell::new(0));
+    let mut tester = WidgetTester::new(TapCounter { taps: taps.clone() });
+    tester.pump();
+
+    tester.tap((10.0, 10.0));
+    assert_eq!(taps.get(), 1);
+
+    tester.tap((100.0, 100.0));
+    assert_eq!(taps.get(), 2);
+}
+
+#[test]
+fn drag_moves_touch_from_start_to_end() {
+    let locations = Rc::new(RefCell::new(Vec::new()));
+    let ended = Rc::new(Cell::new(false));
+    let mut tester = WidgetTester::new(TouchRecorder {
+        locations: locations.clone(),
+        ended: ended.clone(),
+    });
+    tester.pump();
+
+    tester.drag((10.0, 10.0), (210.0, 10.0));
+
+    let locations = locations.borrow();
+    assert_eq!(*locations.first().unwrap(), Point::new(10.0, 10.0));
+    assert_eq!(*locations.last().unwrap(), Point::new(210.0, 10.0));
+    assert!(locations.len() > 2);
+    assert!(ended.get());
+}
+
+#[test]
+fn send_key_reaches_responder() {
+    let keys = Rc::new(RefCell::new(Vec::new()));
+    let mut tester = WidgetTester::new(KeyRecorder { keys: keys.clone() });
+    tester.pump();
+
+    tester.send_key(press(Key::A, 'a'));
+    assert!(keys.borrow().is_empty(), "keys are only sent to a responder");
+
+    tester.tap((10.0, 10.0));
+    tester.send_key(press(Key::A, 'a'));
+    tester.send_key(press(Key::B, 'b'));
+    assert_eq!(*keys.borrow(), vec![Key::A, Key::B]);
+}
+
+#[test]
+fn find_by_type_and_text() {
+    let mut tester = WidgetTester::new(
+        Column::default()
+            .with_child(Text::new_text("first"))
+            .with_child(Text::new_text("second"))
+            .with_child(Decoration::new_empty(Color::WHITE, None)),
+    );
+    tester.pump();
+
+    assert_eq!(tester.find_by_type::<Text>().len(), 2);
+    assert_eq!(tester.find_by_type::<Decoration>().len(), 1);
+    assert_eq!(tester.find_by_type::<Gesture>().len(), 0);
+
+    let second = tester.find_by_text("second");
+    assert_eq!(second.len(), 1);
+    assert_eq!(tester.widget::<Text>(second[0]).unwrap().text(), "second");
+    assert!(tester.find_by_text("third").is_empty());
+}
+
+#[test]
+fn pump_for_advances_clock_to_fire_timer() {
+    let fired = Rc::new(Cell::new(false));
+    let mut tester = WidgetTester::new(Timeout { fired: fired.clone() });
+    tester.pump();
+
+    tester.pump_for(Duration::from_millis(300));
+    assert!(!fired.get());
+    assert_eq!(tester.clock().elapsed(), Duration::from_millis(300));
+
+    tester.pump_for(Duration::from_millis(300));
+    assert!(fired.get());
+}
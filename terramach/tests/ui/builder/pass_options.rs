@@ -0,0 +1,52 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach::WidgetBuilder;
+
+#[derive(WidgetBuilder)]
+struct Label {
+    #[builder(required)]
+    text: String,
+    #[builder(required)]
+    size: f32,
+    #[builder(skip)]
+    lines_cache: Vec<String>,
+    #[builder(strip_option)]
+    hint: Option<String>,
+    color: Option<u32>,
+    #[builder(default = "4")]
+    max_lines: usize,
+}
+
+fn main() {
+    let label = Label::builder("Hello", 12.0);
+    assert_eq!(label.text, "Hello");
+    assert_eq!(label.size, 12.0);
+    assert!(label.lines_cache.is_empty());
+    assert_eq!(label.hint, None);
+    assert_eq!(label.color, None);
+    assert_eq!(label.max_lines, 4);
+
+    let label = Label::builder("Hello", 12.0)
+        .with_hint("Say hello")
+        .with_color(0xFF00FF00)
+        .with_max_lines(2);
+    assert_eq!(label.hint.as_deref(), Some("Say hello"));
+    assert_eq!(label.color, Some(0xFF00FF00));
+    assert_eq!(label.max_lines, 2);
+}
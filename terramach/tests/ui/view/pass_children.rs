@@ -0,0 +1,40 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach::view;
+use terramach::widgets::*;
+
+fn main() {
+    let items = vec!["a", "b"];
+    let show = true;
+    let _ = view! {
+        Row(horizontal_alignment = MainAxisAlignment::End) {
+            #[flex(1)]
+            Text::new_text("flex"),
+            if show {
+                Text::new_text("shown"),
+            } else {
+                Text::new_text("hidden"),
+            }
+            for item in &items {
+                Text::new_text(*item),
+            }
+            { Text::new_text("expression") },
+        }
+    };
+}
@@ -0,0 +1,144 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use terramach::testing::WidgetTester;
+use terramach::*;
+
+const TIMEOUT: usize = 1;
+
+type Log = Rc<RefCell<Vec<&'static str>>>;
+
+#[derive(Clone, PartialWidget)]
+struct Host {
+    shown: Rc<Cell<bool>>,
+    handle: Rc<RefCell<Option<WidgetHandle>>>,
+    probe: Probe,
+}
+
+impl PartialEq for Host {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.shown, &other.shown)
+    }
+}
+
+impl Widget for Host {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        *self.handle.borrow_mut() = Some(context.handle());
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        if self.shown.get() {
+            build.add_child(Outer { probe: self.probe.clone() });
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Outer {
+    probe: Probe,
+}
+
+impl Widget for Outer {
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(Inner { probe: self.probe.clone() });
+    }
+
+    fn unmount(&self, _: &mut WidgetContext) {
+        self.probe.log.borrow_mut().push("outer");
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget)]
+struct Inner {
+    probe: Probe,
+}
+
+impl Widget for Inner {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        context.schedule_timer(TIMEOUT, Timer::new(Duration::from_millis(100), None));
+        let (resolved, finished) = (self.probe.resolved.clone(), self.probe.finished.clone());
+        context.spawn(async move {
+            let _ = spawn_blocking(move || {
+                thread::sleep(Duration::from_millis(100));
+                finished.store(true, Ordering::SeqCst);
+            })
+            .await;
+            resolved.set(true);
+        });
+    }
+
+    fn event(&self, _: &mut WidgetContext, event: &mut EventContext) {
+        if let Event::Timer(TIMEOUT) = event.get() {
+            self.probe.fired.set(true);
+        }
+    }
+
+    fn unmount(&self, _: &mut WidgetContext) {
+        self.probe.log.borrow_mut().push("inner");
+    }
+}
+
+#[derive(Clone, Default)]
+struct Probe {
+    log: Log,
+    fired: Rc<Cell<bool>>,
+    resolved: Rc<Cell<bool>>,
+    finished: Arc<AtomicBool>,
+}
+
+impl PartialEq for Probe {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.log, &other.log)
+    }
+}
+
+#[test]
+fn removed_subtree_unmounts_bottom_up_and_releases_timers_and_tasks() {
+    let shown = Rc::new(Cell::new(true));
+    let handle = Rc::new(RefCell::new(None));
+    let probe = Probe::default();
+    let mut tester = WidgetTester::new(Host {
+        shown: shown.clone(),
+        handle: handle.clone(),
+        probe: probe.clone(),
+    });
+    tester.pump();
+    assert_eq!(tester.find_by_type::<Inner>().len(), 1);
+
+    shown.set(false);
+    handle.borrow().as_ref().unwrap().mark_need_build();
+    tester.pump();
+    tester.pump();
+    assert!(tester.find_by_type::<Outer>().is_empty());
+    assert_eq!(*probe.log.borrow(), vec!["inner", "outer"]);
+
+    while !probe.finished.load(Ordering::SeqCst) {
+        thread::sleep(Duration::from_millis(1));
+    }
+    tester.pump_for(Duration::from_millis(200));
+
+    assert!(!probe.fired.get(), "a timer fired after unmount");
+    assert!(!probe.resolved.get(), "a task resolved after unmount");
+}
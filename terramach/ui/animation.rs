@@ -20,6 +20,8 @@ use std::time::{Duration, Instant};
 
 use terramach_graphics::{Rect, Size};
 
+use crate::Clock;
+
 pub trait Interpolation {
     fn interpolate(&self, t: f32) -> f32;
 }
@@ -33,11 +35,11 @@ impl<F: Fn(f32) -> f32> Interpolation for F {
 pub trait Driver {
     fn is_driving(&self) -> bool;
 
-    fn start(&mut self);
+    fn start(&mut self, clock: &dyn Clock);
 
     fn stop(&mut self) {}
 
-    fn advance(&mut self);
+    fn advance(&mut self, clock: &dyn Clock);
 
     fn value(&self) -> f32;
 }
@@ -84,16 +86,16 @@ impl<T> Animation<T> {
         self.driver.is_driving()
     }
 
-    pub fn start(&mut self) {
-        self.driver.start();
+    pub fn start(&mut self, clock: &dyn Clock) {
+        self.driver.start(clock);
     }
 
     pub fn stop(&mut self) {
         self.driver.stop();
     }
 
-    pub fn advance(&mut self) -> bool {
-        self.driver.advance();
+    pub fn advance(&mut self, clock: &dyn Clock) -> bool {
+        self.driver.advance(clock);
         self.driver.is_driving()
     }
 
@@ -128,23 +130,25 @@ impl Driver for DurationDriver {
         self.timestamp.is_some()
     }
 
-    fn start(&mut self) {
-        self.timestamp = Some(Instant::now());
+    fn start(&mut self, clock: &dyn Clock) {
+        self.timestamp = Some(clock.now());
     }
 
     fn stop(&mut self) {
         self.timestamp = None;
     }
 
-    fn advance(&mut self) {
+    fn advance(&mut self, clock: &dyn Clock) {
+        let now = clock.now();
         if self.timestamp.is_none() {
-            self.timestamp = Some(Instant::now());
+            self.timestamp = Some(now);
         }
         if let Some(timestamp) = &self.timestamp {
             if self.value == 1.0 {
                 self.timestamp = None;
             } else {
-                self.value = (timestamp.elapsed().as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
+                let elapsed = now.saturating_duration_since(*timestamp);
+                self.value = (elapsed.as_secs_f32() / self.duration.as_secs_f32()).min(1.0);
             }
         }
     }
@@ -16,179 +16,242 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
-use crate::gpu::Pipeline;
+use crate::gpu::{FrameTimingsHandler, Pipeline};
 use crate::platform::{RunLoop, SharedRunLoop, VSync, Cursors};
-use crate::{AppEvent, AppEvents, DrawContext, Event, EventResponder, RenderTree, Touches, BoxedWidget};
+use crate::{AppEvent, AppEvents, DrawContext, Event, EventResponder, RenderTree, Touches, BoxedWidget, ErrorHandler, InspectorHandler, HitKey, Key, KeyAction, DebugPaintSettings};
 
 use terramach_graphics::{Canvas, Display, PictureRecorder, Rect, Size};
 
-pub(crate) fn run_app(
-    mut run_loop: RunLoop,
-    mut events: AppEvents,
-    display: impl Display + 'static,
-    content: impl Into<BoxedWidget>,
-) {
-    let content = content.into();
-
-    let mut display = Box::new(display);
-    display.make_current();
-    let mut current_size = display.size();
-    let pixel_ratio = display.pixel_ratio();
-    display.clear_current();
-
-    let mut vsync = VSync::default();
-    let mut pipeline = Pipeline::new(vsync.clone(), display);
-    let mut tree = RenderTree::new(pipeline.share(), content);
+pub(crate) struct AppEventDispatcher {
+    pixel_ratio: f32,
+    hover_responders: Vec<EventResponder>,
+    current_responder: Option<EventResponder>,
+    touches: Touches,
+    cursors: Option<Cursors>,
+}
 
-    let mut hover_responders: Vec<EventResponder> = Vec::new();
-    let mut current_responder: Option<EventResponder> = None;
-    let mut touches = Touches::new();
-    let mut cursors = Cursors::new();
+impl AppEventDispatcher {
+    pub fn new(pixel_ratio: f32, cursors: impl Into<Option<Cursors>>) -> Self {
+        AppEventDispatcher {
+            pixel_ratio,
+            hover_responders: Vec::new(),
+            current_responder: None,
+            touches: Touches::new(),
+            cursors: cursors.into(),
+        }
+    }
 
-    let mut inner_run_loop = run_loop.share();
-    run_loop.add_observer(move || {
-        if let Some(events) = events.poll() {
-            let mut issue_touch = false;
-            for event in events {
-                match event {
-                    AppEvent::Quit => {
-                        inner_run_loop.stop();
-                    }
-                    AppEvent::Resize(size) => {
-                        current_size = size;
-                        tree.invalidate();
-                        pipeline.resize(size);
+    pub fn dispatch(&mut self, tree: &mut RenderTree, events: Vec<AppEvent>) {
+        let mut issue_touch = false;
+        for event in events {
+            match event {
+                AppEvent::Quit | AppEvent::Resize(_) => {}
+                AppEvent::Scroll(delta) => {
+                    if let Some(responder) = self.hover_responders.last() {
+                        tree.emit_event(
+                            responder.widget(),
+                            Event::Scroll(delta * self.pixel_ratio),
+                        );
                     }
-                    AppEvent::Scroll(delta) => {
-                        if let Some(responder) = hover_responders.last() {
-                            tree.emit_event(
-                                responder.widget(),
-                                Event::Scroll(delta * pixel_ratio),
-                            );
-                        }
-                    }
-                    AppEvent::Focus(focused) => {
-                        if let Some(responder) = &current_responder {
-                            tree.emit_event(
-                                responder.widget(),
-                                Event::Focus(focused),
-                            );
-                        }
+                }
+                AppEvent::Focus(focused) => {
+                    if let Some(responder) = &self.current_responder {
+                        tree.emit_event(
+                            responder.widget(),
+                            Event::Focus(focused),
+                        );
                     }
-                    AppEvent::TouchBegin(touch) => {
-                        let new_responder = tree.hit_test(None, touch.location());
-                        if let Some(responder) = &current_responder {
-                            if let Some(new_responder) = new_responder {
-                                if new_responder.widget() != responder.widget() {
-                                    tree.emit_event(responder.widget(), Event::ResignedResponder);
-                                    tree.emit_event(new_responder.widget(), Event::BecameResponder);
-                                    current_responder = Some(new_responder);
-                                }
-                            } else {
+                }
+                AppEvent::TouchBegin(touch) => {
+                    let new_responder = tree.hit_test(None, touch.location());
+                    if let Some(responder) = &self.current_responder {
+                        if let Some(new_responder) = new_responder {
+                            if new_responder.widget() != responder.widget() {
                                 tree.emit_event(responder.widget(), Event::ResignedResponder);
-                                current_responder = None;
+                                tree.emit_event(new_responder.widget(), Event::BecameResponder);
+                                self.current_responder = Some(new_responder);
                             }
-                        } else if let Some(responder) = new_responder {
-                            tree.emit_event(responder.widget(), Event::BecameResponder);
-                            current_responder = Some(responder);
+                        } else {
+                            tree.emit_event(responder.widget(), Event::ResignedResponder);
+                            self.current_responder = None;
                         }
+                    } else if let Some(responder) = new_responder {
+                        tree.emit_event(responder.widget(), Event::BecameResponder);
+                        self.current_responder = Some(responder);
+                    }
 
-                        if let Some(responder) = &current_responder {
-                            touches.update(responder.transform_touch(&touch));
-                            issue_touch = true;
-                            tree.emit_event(
-                                responder.widget(),
-                                Event::TouchBegin(responder.transform_touch(&touch)),
-                            );
-                        }
+                    if let Some(responder) = &self.current_responder {
+                        self.touches.update(responder.transform_touch(&touch));
+                        issue_touch = true;
+                        tree.emit_event(
+                            responder.widget(),
+                            Event::TouchBegin(responder.transform_touch(&touch)),
+                        );
                     }
-                    AppEvent::TouchUpdate(touch) => {
-                        if let Some(responder) = &current_responder {
-                            touches.update(responder.transform_touch(&touch));
-                            issue_touch = true;
-                            tree.emit_event(
-                                responder.widget(),
-                                Event::TouchUpdate(responder.transform_touch(&touch)),
-                            );
-                        }
+                }
+                AppEvent::TouchUpdate(touch) => {
+                    if let Some(responder) = &self.current_responder {
+                        self.touches.update(responder.transform_touch(&touch));
+                        issue_touch = true;
+                        tree.emit_event(
+                            responder.widget(),
+                            Event::TouchUpdate(responder.transform_touch(&touch)),
+                        );
                     }
-                    AppEvent::TouchEnd(touch) => {
-                        if let Some(responder) = &current_responder {
-                            touches.remove(touch.id());
-                            issue_touch = true;
-                            tree.emit_event(
-                                responder.widget(),
-                                Event::TouchEnd(responder.transform_touch(&touch)),
-                            );
-                        }
+                }
+                AppEvent::TouchEnd(touch) => {
+                    if let Some(responder) = &self.current_responder {
+                        self.touches.remove(touch.id());
+                        issue_touch = true;
+                        tree.emit_event(
+                            responder.widget(),
+                            Event::TouchEnd(responder.transform_touch(&touch)),
+                        );
                     }
-                    AppEvent::Hover(location) => {
-                        // scan through hover stack from first, if one is not hover, the rest aren't either
-                        for i in 0..hover_responders.len() {
-                            let responder = &hover_responders[i];
-                            let hit_responder = tree.hit_test(
-                                responder.widget(),
-                                responder.transform_point(location),
-                            );
-                            if hit_responder.is_none() {
-                                for j in hover_responders.len() - 1..=i {
-                                    let responder = &hover_responders[j];
-                                    if responder.has_cursor() {
+                }
+                AppEvent::Hover(location) => {
+                    // scan through hover stack from first, if one is not hover, the rest aren't either
+                    for i in 0..self.hover_responders.len() {
+                        let responder = &self.hover_responders[i];
+                        let hit_responder = tree.hit_test(
+                            responder.widget(),
+                            responder.transform_point(location),
+                        );
+                        if hit_responder.is_none() {
+                            for j in self.hover_responders.len() - 1..=i {
+                                let responder = &self.hover_responders[j];
+                                if responder.has_cursor() {
+                                    if let Some(cursors) = &mut self.cursors {
                                         cursors.pop();
                                     }
-                                    tree.emit_event(responder.widget(), Event::Leave);
                                 }
-                                hover_responders = hover_responders.drain(0..i).collect();
-                                break;
+                                tree.emit_event(responder.widget(), Event::Leave);
                             }
+                            self.hover_responders = self.hover_responders.drain(0..i).collect();
+                            break;
                         }
+                    }
 
-                        // check last hover, whether it hits a child, if so add it to the stack, otherwise hit whole tree
-                        if let Some(responder) = hover_responders.last() {
-                            let hit_responder = tree.hit_test(
-                                responder.widget(),
-                                responder.transform_point(location),
-                            );
-                            if let Some(hit_responder) = hit_responder {
-                                if hit_responder.widget() != responder.widget() {
-                                    if let Some(cursor) = hit_responder.cursor() {
+                    // check last hover, whether it hits a child, if so add it to the stack, otherwise hit whole tree
+                    if let Some(responder) = self.hover_responders.last() {
+                        let hit_responder = tree.hit_test(
+                            responder.widget(),
+                            responder.transform_point(location),
+                        );
+                        if let Some(hit_responder) = hit_responder {
+                            if hit_responder.widget() != responder.widget() {
+                                if let Some(cursor) = hit_responder.cursor() {
+                                    if let Some(cursors) = &mut self.cursors {
                                         cursors.push(cursor);
                                     }
-                                    tree.emit_event(hit_responder.widget(), Event::Enter);
-                                    hover_responders.push(hit_responder);
                                 }
+                                tree.emit_event(hit_responder.widget(), Event::Enter);
+                                self.hover_responders.push(hit_responder);
                             }
-                        } else if let Some(responder) = tree.hit_test(None, location) {
-                            if let Some(cursor) = responder.cursor() {
+                        }
+                    } else if let Some(responder) = tree.hit_test(None, location) {
+                        if let Some(cursor) = responder.cursor() {
+                            if let Some(cursors) = &mut self.cursors {
                                 cursors.push(cursor);
                             }
-                            tree.emit_event(responder.widget(), Event::Enter);
-                            hover_responders.push(responder);
                         }
+                        tree.emit_event(responder.widget(), Event::Enter);
+                        self.hover_responders.push(responder);
+                    }
 
-                        if let Some(responder) = hover_responders.last() {
-                            tree.emit_event(
-                                responder.widget(),
-                                Event::Hover(responder.transform_point(location)),
-                            );
-                        }
+                    if let Some(responder) = self.hover_responders.last() {
+                        tree.emit_event(
+                            responder.widget(),
+                            Event::Hover(responder.transform_point(location)),
+                        );
                     }
-                    AppEvent::Frame(timestamp) => tree.emit_event(None, Event::Frame(timestamp)),
-                    AppEvent::Key(key) => {
-                        if let Some(responder) = &current_responder {
-                            tree.emit_event(responder.widget(), Event::Key(key));
-                        }
+                }
+                AppEvent::Frame(timestamp) => tree.emit_event(None, Event::Frame(timestamp)),
+                AppEvent::Key(key) => {
+                    if let Some(responder) = &self.current_responder {
+                        tree.emit_event(responder.widget(), Event::Key(key));
                     }
                 }
             }
-            if issue_touch {
-                if let Some(responder) = &current_responder {
-                    tree.emit_event(
-                        responder.widget(),
-                        Event::Touch(touches.clone()),
-                    );
+        }
+        if issue_touch {
+            if let Some(responder) = &self.current_responder {
+                tree.emit_event(
+                    responder.widget(),
+                    Event::Touch(self.touches.clone()),
+                );
+            }
+        }
+    }
+}
+
+fn is_inspector_chord(key: &HitKey) -> bool {
+    cfg!(debug_assertions)
+        && key.action() == KeyAction::Press
+        && key.key() == Key::D
+        && key.modifiers().is_control()
+        && key.modifiers().is_shift()
+}
+
+#[cfg(feature = "inspector")]
+fn inspect(inspector: &mut InspectorHandler, tree: &RenderTree) {
+    inspector(&tree.inspect());
+}
+
+#[cfg(not(feature = "inspector"))]
+fn inspect(inspector: &mut InspectorHandler, _: &RenderTree) {
+    match *inspector {}
+}
+
+pub(crate) fn run_app(
+    mut run_loop: RunLoop,
+    mut events: AppEvents,
+    display: impl Display + 'static,
+    content: impl Into<BoxedWidget>,
+    error_handler: Option<ErrorHandler>,
+    debug_paint: DebugPaintSettings,
+    frame_timings: Option<FrameTimingsHandler>,
+    mut inspector: Option<InspectorHandler>,
+) {
+    let content = content.into();
+
+    let mut display = Box::new(display);
+    display.make_current();
+    let mut current_size = display.size();
+    let pixel_ratio = display.pixel_ratio();
+    display.clear_current();
+
+    let mut vsync = VSync::default();
+    let mut pipeline = Pipeline::new(vsync.clone(), display, frame_timings);
+    debug_paint.set_run_loop(run_loop.share());
+    let mut tree = RenderTree::new(pipeline.share(), content)
+        .with_error_handler(error_handler)
+        .with_debug_paint(debug_paint)
+        .with_run_loop(run_loop.share());
+    let mut dispatcher = AppEventDispatcher::new(pixel_ratio, Cursors::new());
+
+    let mut inner_run_loop = run_loop.share();
+    run_loop.add_observer(move || {
+        if let Some(events) = events.poll() {
+            for event in &events {
+                match event {
+                    AppEvent::Quit => {
+                        inner_run_loop.stop();
+                    }
+                    AppEvent::Resize(size) => {
+                        current_size = *size;
+                        tree.invalidate();
+                        pipeline.resize(*size);
+                    }
+                    AppEvent::Key(key) if is_inspector_chord(key) => {
+                        if let Some(inspector) = &mut inspector {
+                            inspect(inspector, &tree);
+                        }
+                    }
+                    _ => {}
                 }
             }
+            dispatcher.dispatch(&mut tree, events);
         }
 
         tree.render(current_size);
@@ -0,0 +1,35 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use serde_json::{json, Value};
+
+use terramach_graphics::{Point, Size};
+
+pub fn inspect_size(size: Size) -> Value {
+    json!({
+        "width": size.width,
+        "height": size.height,
+    })
+}
+
+pub fn inspect_point(point: Point) -> Value {
+    json!({
+        "x": point.x,
+        "y": point.y,
+    })
+}
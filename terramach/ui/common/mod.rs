@@ -17,7 +17,11 @@
  */
 
 mod index_pool;
+#[cfg(feature = "inspector")]
+mod inspect;
 mod tree;
 
 pub use index_pool::*;
+#[cfg(feature = "inspector")]
+pub use inspect::*;
 pub use tree::*;
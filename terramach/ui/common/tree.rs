@@ -155,6 +155,17 @@ impl<T> Tree<T> {
         Some(removed)
     }
 
+    pub fn reorder(&mut self, parent: impl Into<Option<Id>>, order: &[Id]) {
+        let parent = parent.into().unwrap_or(self.root);
+        if let Some(children) = self.parent_children.get_mut(&parent) {
+            let positions: HashMap<Id, usize> = order.iter()
+                .enumerate()
+                .map(|(position, id)| (*id, position))
+                .collect();
+            children.sort_by_key(|child| positions.get(child).copied().unwrap_or(order.len()));
+        }
+    }
+
     pub fn node(&self, id: Id) -> Option<&T> {
         self.nodes.get(&id)?.deref().as_ref()
     }
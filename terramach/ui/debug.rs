@@ -0,0 +1,179 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::sync::{Arc, Mutex};
+
+use flagset::{FlagSet, flags};
+
+use terramach_graphics::{Canvas, ClipOp, Color, Color4f, Paint, PaintStyle, Point, Rect, Size};
+
+use crate::{ContainerLayer, PaintContext};
+use crate::platform::SharedRunLoop;
+
+flags! {
+    pub enum DebugPaint: u8 {
+        LayoutBounds,
+        RepaintRainbow,
+        Overflow,
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct DebugPaintSettings {
+    flags: Arc<Mutex<FlagSet<DebugPaint>>>,
+    run_loop: Arc<Mutex<Option<SharedRunLoop>>>,
+}
+
+impl DebugPaintSettings {
+    pub fn new(flags: impl Into<FlagSet<DebugPaint>>) -> Self {
+        DebugPaintSettings {
+            flags: Arc::new(Mutex::new(flags.into())),
+            run_loop: Default::default(),
+        }
+    }
+
+    pub(crate) fn set_run_loop(&self, run_loop: impl Into<Option<SharedRunLoop>>) {
+        if let Ok(mut current) = self.run_loop.lock() {
+            *current = run_loop.into();
+        }
+    }
+
+    pub fn flags(&self) -> FlagSet<DebugPaint> {
+        self.flags.lock().map(|flags| *flags).unwrap_or_default()
+    }
+
+    pub fn is_enabled(&self, flag: DebugPaint) -> bool {
+        self.flags() & flag == flag
+    }
+
+    pub fn set(&self, flags: impl Into<FlagSet<DebugPaint>>) {
+        if let Ok(mut current) = self.flags.lock() {
+            *current = flags.into();
+        }
+        self.wakeup();
+    }
+
+    pub fn toggle(&self, flags: impl Into<FlagSet<DebugPaint>>) {
+        if let Ok(mut current) = self.flags.lock() {
+            let flags = flags.into();
+            *current = (*current | flags) & !(*current & flags);
+        }
+        self.wakeup();
+    }
+
+    fn wakeup(&self) {
+        if let Ok(mut run_loop) = self.run_loop.lock() {
+            if let Some(run_loop) = run_loop.as_mut() {
+                run_loop.wakeup();
+            }
+        }
+    }
+}
+
+const BOUNDS_COLOR: Color = Color::new(0xFF00B7FF);
+const PADDING_COLOR: Color = Color::new(0x3000B7FF);
+const OVERFLOW_COLOR: Color = Color::new(0xB0FFD600);
+const OVERFLOW_STRIPE_COLOR: Color = Color::new(0xB0D50000);
+const OVERFLOW_EXTENT: f32 = 8.0;
+const OVERFLOW_STRIPE_WIDTH: f32 = 4.0;
+const RAINBOW_COLORS: [Color; 6] = [
+    Color::new(0x30FF0000),
+    Color::new(0x30FF9900),
+    Color::new(0x30FFFF00),
+    Color::new(0x3000FF00),
+    Color::new(0x300099FF),
+    Color::new(0x309900FF),
+];
+
+/// How far the children stick out of their parent's size.
+pub(crate) fn layout_overflow(size: Size, children: &[Rect]) -> Size {
+    children.iter().fold(Size::default(), |overflow, child| {
+        Size::new(
+            overflow.width.max(child.right - size.width),
+            overflow.height.max(child.bottom - size.height),
+        )
+    })
+}
+
+pub(crate) fn paint_debug_overlay(
+    flags: FlagSet<DebugPaint>,
+    size: Size,
+    children: &[Rect],
+    overflow: Size,
+    paint_count: Option<u64>,
+) -> Option<ContainerLayer> {
+    let mut paint = PaintContext::new(size);
+    let bounds = Rect::from_size(size);
+    if flags & DebugPaint::RepaintRainbow == DebugPaint::RepaintRainbow {
+        if let Some(paint_count) = paint_count {
+            let color = RAINBOW_COLORS[paint_count as usize % RAINBOW_COLORS.len()];
+            paint.canvas().draw_rect(bounds, &Paint::new(Color4f::from(color), None));
+        }
+    }
+    if flags & DebugPaint::LayoutBounds == DebugPaint::LayoutBounds {
+        paint_layout_bounds(paint.canvas(), bounds, children);
+    }
+    if flags & DebugPaint::Overflow == DebugPaint::Overflow {
+        paint_overflow(paint.canvas(), size, overflow);
+    }
+    paint.take_layers()
+}
+
+fn paint_layout_bounds(canvas: &mut Canvas, bounds: Rect, children: &[Rect]) {
+    if let [child] = children {
+        let count = canvas.save();
+        canvas.clip_rect(*child, ClipOp::Difference, None);
+        canvas.draw_rect(bounds, &Paint::new(Color4f::from(PADDING_COLOR), None));
+        canvas.restore_to_count(count);
+    }
+    let mut outline = Paint::new(Color4f::from(BOUNDS_COLOR), None);
+    outline.set_style(PaintStyle::Stroke);
+    outline.set_stroke_width(1.0);
+    canvas.draw_rect(bounds.with_inset((0.5, 0.5)), &outline);
+}
+
+fn paint_overflow(canvas: &mut Canvas, size: Size, overflow: Size) {
+    let allowed = Rect::from_size(size);
+    if overflow.width > 0.0 {
+        let extent = OVERFLOW_EXTENT.min(allowed.width());
+        paint_stripes(canvas, Rect::new(allowed.right - extent, allowed.top, allowed.right, allowed.bottom));
+    }
+    if overflow.height > 0.0 {
+        let extent = OVERFLOW_EXTENT.min(allowed.height());
+        paint_stripes(canvas, Rect::new(allowed.left, allowed.bottom - extent, allowed.right, allowed.bottom));
+    }
+}
+
+fn paint_stripes(canvas: &mut Canvas, rect: Rect) {
+    let count = canvas.save();
+    canvas.clip_rect(rect, None, None);
+    canvas.draw_rect(rect, &Paint::new(Color4f::from(OVERFLOW_COLOR), None));
+    let mut stripe = Paint::new(Color4f::from(OVERFLOW_STRIPE_COLOR), None);
+    stripe.set_style(PaintStyle::Stroke);
+    stripe.set_stroke_width(OVERFLOW_STRIPE_WIDTH);
+    let mut x = rect.left - rect.height();
+    while x < rect.right {
+        canvas.draw_line(
+            Point::new(x, rect.bottom),
+            Point::new(x + rect.height(), rect.top),
+            &stripe,
+        );
+        x += OVERFLOW_STRIPE_WIDTH * 2.0;
+    }
+    canvas.restore_to_count(count);
+}
@@ -0,0 +1,276 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use terramach_graphics::Size;
+
+use crate::{BoxedWidget, Constraints, Fit, WidgetKey};
+use crate::description::{DescriptionError, DescriptionNode, WidgetRegistry};
+use crate::widgets::*;
+
+type BuildResult = Result<BoxedWidget, DescriptionError>;
+
+pub(crate) fn register_widgets(registry: &mut WidgetRegistry) {
+    registry.register("Align", build_align);
+    registry.register("AspectRatio", build_aspect_ratio);
+    registry.register("Baseline", build_baseline);
+    registry.register("Column", build_column);
+    registry.register("Constrained", build_constrained);
+    registry.register("Decoration", build_decoration);
+    registry.register("DefaultTextStyle", build_default_text_style);
+    registry.register("ErrorBoundary", build_error_boundary);
+    registry.register("Fractional", build_fractional);
+    registry.register("Image", build_image);
+    registry.register("IntrinsicHeight", build_intrinsic_height);
+    registry.register("IntrinsicWidth", build_intrinsic_width);
+    registry.register("Keyed", build_keyed);
+    registry.register("Opacity", build_opacity);
+    registry.register("Padding", build_padding);
+    registry.register("RepaintBoundary", build_repaint_boundary);
+    registry.register("Row", build_row);
+    registry.register("Scrollable", build_scrollable);
+    registry.register("Stack", build_stack);
+    registry.register("Text", build_text);
+}
+
+fn alignment(node: &DescriptionNode) -> Result<Option<Alignment>, DescriptionError> {
+    let alignment = node.choice("alignment", &[
+        ("top_left", Alignment::top_left()),
+        ("top_center", Alignment::top_center()),
+        ("top_right", Alignment::top_right()),
+        ("left_center", Alignment::left_center()),
+        ("center", Alignment::center()),
+        ("right_center", Alignment::right_center()),
+        ("bottom_left", Alignment::bottom_left()),
+        ("bottom_center", Alignment::bottom_center()),
+        ("bottom_right", Alignment::bottom_right()),
+    ])?;
+    if alignment.is_some() {
+        return Ok(alignment);
+    }
+    let horizontal = node.f32("horizontal")?;
+    let vertical = node.f32("vertical")?;
+    if horizontal.is_some() || vertical.is_some() {
+        Ok(Some(Alignment::new(horizontal, vertical)))
+    } else {
+        Ok(None)
+    }
+}
+
+fn main_axis_alignment(node: &DescriptionNode, name: &str) -> Result<Option<MainAxisAlignment>, DescriptionError> {
+    node.choice(name, &[
+        ("start", MainAxisAlignment::Start),
+        ("middle", MainAxisAlignment::Middle),
+        ("end", MainAxisAlignment::End),
+    ])
+}
+
+fn cross_axis_alignment(node: &DescriptionNode, name: &str) -> Result<Option<CrossAxisAlignment>, DescriptionError> {
+    node.choice(name, &[
+        ("start", CrossAxisAlignment::Start),
+        ("middle", CrossAxisAlignment::Middle),
+        ("end", CrossAxisAlignment::End),
+        ("stretch", CrossAxisAlignment::Stretch),
+        ("baseline", CrossAxisAlignment::Baseline),
+    ])
+}
+
+fn text_style(node: &DescriptionNode) -> Result<Option<TextStyle>, DescriptionError> {
+    let color = node.color("color")?;
+    let background_color = node.color("background_color")?;
+    let font_size = node.f32("font_size")?;
+    let font_families = node.strings("font_families")?;
+    if color.is_none() && background_color.is_none() && font_size.is_none() && font_families.is_none() {
+        return Ok(None);
+    }
+    let mut text_style = TextStyle::default()
+        .with_color(color)
+        .with_background_color(background_color)
+        .with_font_size(font_size);
+    if let Some(font_families) = font_families {
+        text_style = text_style.with_font_families(&font_families[..]);
+    }
+    Ok(Some(text_style))
+}
+
+fn flex_children(node: &DescriptionNode) -> Result<Vec<(Option<usize>, BoxedWidget)>, DescriptionError> {
+    node.children()
+        .iter()
+        .map(|child| {
+            let flex = child.u64("flex")?.map(|flex| flex as usize);
+            Ok((flex, child.build()?))
+        })
+        .collect()
+}
+
+fn build_align(node: &DescriptionNode) -> BuildResult {
+    Ok(Align::new(alignment(node)?, node.build_required_child()?).into())
+}
+
+fn build_aspect_ratio(node: &DescriptionNode) -> BuildResult {
+    let ratio = node.required("ratio", node.f32("ratio")?)?;
+    Ok(match node.build_child()? {
+        Some(child) => AspectRatio::new(ratio, child).into(),
+        None => AspectRatio::new_empty(ratio).into(),
+    })
+}
+
+fn build_baseline(node: &DescriptionNode) -> BuildResult {
+    let baseline = node.required("baseline", node.f32("baseline")?)?;
+    Ok(Baseline::new(baseline, node.build_required_child()?).into())
+}
+
+fn build_column(node: &DescriptionNode) -> BuildResult {
+    let mut column = Column::new(
+        cross_axis_alignment(node, "horizontal_alignment")?.unwrap_or(CrossAxisAlignment::Middle),
+        main_axis_alignment(node, "vertical_alignment")?,
+    );
+    for (flex, child) in flex_children(node)? {
+        column = column.with_flex_child(flex, child);
+    }
+    Ok(column.into())
+}
+
+fn build_constrained(node: &DescriptionNode) -> BuildResult {
+    let constraints = Constraints::new(
+        Size::new(
+            node.f32("min_width")?.unwrap_or_default(),
+            node.f32("min_height")?.unwrap_or_default(),
+        ),
+        Size::new(
+            node.f32("max_width")?.unwrap_or(f32::INFINITY),
+            node.f32("max_height")?.unwrap_or(f32::INFINITY),
+        ),
+    );
+    Ok(match node.build_child()? {
+        Some(child) => Constrained::new(constraints, child).into(),
+        None => Constrained::new_empty(constraints).into(),
+    })
+}
+
+fn build_decoration(node: &DescriptionNode) -> BuildResult {
+    let background_color = node.color("background_color")?;
+    let border_radius = node.f32("border_radius")?.map(BorderRadius::new_all);
+    Ok(match node.build_child()? {
+        Some(child) => Decoration::new(background_color, border_radius, child).into(),
+        None => Decoration::new_empty(background_color, border_radius).into(),
+    })
+}
+
+fn build_default_text_style(node: &DescriptionNode) -> BuildResult {
+    Ok(DefaultTextStyle::new(text_style(node)?, node.build_required_child()?).into())
+}
+
+fn build_error_boundary(node: &DescriptionNode) -> BuildResult {
+    Ok(ErrorBoundary::new(node.build_required_child()?).into())
+}
+
+fn build_fractional(node: &DescriptionNode) -> BuildResult {
+    let fraction = Size::new(
+        node.f32("width")?.unwrap_or(1.0),
+        node.f32("height")?.unwrap_or(1.0),
+    );
+    Ok(match node.build_child()? {
+        Some(child) => Fractional::new(fraction, child).into(),
+        None => Fractional::new_empty(fraction).into(),
+    })
+}
+
+fn build_image(node: &DescriptionNode) -> BuildResult {
+    let path = node.required("path", node.string("path")?)?;
+    let bytes = std::fs::read(path)
+        .map_err(|error| node.error(format!("Failed to read image `{}`: {}", path, error)))?;
+    let fit = node.choice("fit", &[
+        ("contain", Fit::Contain),
+        ("cover", Fit::Cover),
+        ("fill", Fit::Fill),
+    ])?;
+    Ok(Image::from_bytes(alignment(node)?, fit, node.color("color")?, &bytes).into())
+}
+
+fn build_intrinsic_height(node: &DescriptionNode) -> BuildResult {
+    Ok(IntrinsicHeight::new(node.build_required_child()?).into())
+}
+
+fn build_intrinsic_width(node: &DescriptionNode) -> BuildResult {
+    Ok(IntrinsicWidth::new(node.build_required_child()?).into())
+}
+
+fn build_keyed(node: &DescriptionNode) -> BuildResult {
+    let key = match (node.u64("key"), node.string("key")) {
+        (Ok(Some(index)), _) => WidgetKey::from(index),
+        (_, Ok(Some(name))) => WidgetKey::from(name),
+        (Ok(None), _) => return Err(node.error("Missing property `key`")),
+        _ => return Err(node.error("Property `key` must be an unsigned integer or a string")),
+    };
+    Ok(Keyed::new(key, node.build_required_child()?).into())
+}
+
+fn build_opacity(node: &DescriptionNode) -> BuildResult {
+    let opacity = node.required("opacity", node.f32("opacity")?)?;
+    Ok(Opacity::new(opacity, node.build_required_child()?).into())
+}
+
+fn build_padding(node: &DescriptionNode) -> BuildResult {
+    let all = node.f32("all")?;
+    let horizontal = node.f32("horizontal")?.or(all);
+    let vertical = node.f32("vertical")?.or(all);
+    let left = node.f32("left")?.or(horizontal);
+    let top = node.f32("top")?.or(vertical);
+    let right = node.f32("right")?.or(horizontal);
+    let bottom = node.f32("bottom")?.or(vertical);
+    Ok(match node.build_child()? {
+        Some(child) => Padding::new(left, top, right, bottom, child).into(),
+        None => Padding::new_empty(left, top, right, bottom).into(),
+    })
+}
+
+fn build_repaint_boundary(node: &DescriptionNode) -> BuildResult {
+    Ok(RepaintBoundary::new(node.build_required_child()?).into())
+}
+
+fn build_row(node: &DescriptionNode) -> BuildResult {
+    let mut row = Row::new(
+        main_axis_alignment(node, "horizontal_alignment")?,
+        cross_axis_alignment(node, "vertical_alignment")?.unwrap_or(CrossAxisAlignment::Middle),
+    );
+    for (flex, child) in flex_children(node)? {
+        row = row.with_flex_child(flex, child);
+    }
+    Ok(row.into())
+}
+
+fn build_scrollable(node: &DescriptionNode) -> BuildResult {
+    let direction = node.choice("direction", &[
+        ("vertical", ScrollDirection::Vertical),
+        ("horizontal", ScrollDirection::Horizontal),
+    ])?;
+    Ok(Scrollable::new(direction.unwrap_or_default(), node.build_required_child()?).into())
+}
+
+fn build_stack(node: &DescriptionNode) -> BuildResult {
+    let mut stack = Stack::new();
+    for child in node.children() {
+        stack = stack.with_child(child.build()?);
+    }
+    Ok(stack.into())
+}
+
+fn build_text(node: &DescriptionNode) -> BuildResult {
+    let text = node.string("text")?.unwrap_or_default();
+    Ok(Text::new(text, text_style(node)?).into())
+}
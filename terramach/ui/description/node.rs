@@ -0,0 +1,265 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use terramach_graphics::Color;
+
+use crate::BoxedWidget;
+use crate::description::{DescriptionError, WidgetRegistry};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WidgetDescription {
+    #[serde(rename = "type")]
+    widget_type: String,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    properties: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    child: Option<Box<WidgetDescription>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    children: Vec<WidgetDescription>,
+}
+
+impl WidgetDescription {
+    pub fn new(widget_type: impl AsRef<str>) -> Self {
+        WidgetDescription {
+            widget_type: widget_type.as_ref().to_string(),
+            properties: Map::new(),
+            child: None,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn from_json(json: impl AsRef<str>) -> Result<Self, DescriptionError> {
+        serde_json::from_str(json.as_ref())
+            .map_err(|error| {
+                DescriptionError::new("", format!("Invalid JSON: {}", error))
+                    .with_position(error.line(), error.column())
+            })
+    }
+
+    pub fn from_ron(ron: impl AsRef<str>) -> Result<Self, DescriptionError> {
+        ron::de::from_str(ron.as_ref())
+            .map_err(|error| {
+                DescriptionError::new("", format!("Invalid RON: {}", error))
+                    .with_position(error.position.line, error.position.col)
+            })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    pub fn to_ron(&self) -> String {
+        ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()).unwrap_or_default()
+    }
+
+    pub fn with_property(mut self, name: impl AsRef<str>, value: impl Into<Value>) -> Self {
+        self.properties.insert(name.as_ref().to_string(), value.into());
+        self
+    }
+
+    pub fn with_child(mut self, child: WidgetDescription) -> Self {
+        self.child = Some(Box::new(child));
+        self
+    }
+
+    pub fn with_children(mut self, children: Vec<WidgetDescription>) -> Self {
+        self.children = children;
+        self
+    }
+
+    pub fn widget_type(&self) -> &str {
+        &self.widget_type
+    }
+
+    pub fn properties(&self) -> &Map<String, Value> {
+        &self.properties
+    }
+
+    pub fn child(&self) -> Option<&WidgetDescription> {
+        self.child.as_deref()
+    }
+
+    pub fn children(&self) -> &[WidgetDescription] {
+        &self.children
+    }
+}
+
+pub struct DescriptionNode<'a> {
+    description: &'a WidgetDescription,
+    registry: &'a WidgetRegistry,
+    path: String,
+}
+
+impl<'a> DescriptionNode<'a> {
+    pub(crate) fn new(
+        description: &'a WidgetDescription,
+        registry: &'a WidgetRegistry,
+        path: String,
+    ) -> Self {
+        DescriptionNode {
+            description,
+            registry,
+            path,
+        }
+    }
+
+    pub fn description(&self) -> &'a WidgetDescription {
+        self.description
+    }
+
+    pub fn widget_type(&self) -> &'a str {
+        self.description.widget_type()
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn error(&self, message: impl AsRef<str>) -> DescriptionError {
+        DescriptionError::new(&self.path, message)
+    }
+
+    pub fn build(&self) -> Result<BoxedWidget, DescriptionError> {
+        self.registry.build_node(self)
+    }
+
+    pub fn child(&self) -> Option<DescriptionNode<'a>> {
+        let child = self.description.child()?;
+        let path = format!("{}.child({})", self.path, child.widget_type());
+        Some(DescriptionNode::new(child, self.registry, path))
+    }
+
+    pub fn build_child(&self) -> Result<Option<BoxedWidget>, DescriptionError> {
+        self.child().map(|child| child.build()).transpose()
+    }
+
+    pub fn build_required_child(&self) -> Result<BoxedWidget, DescriptionError> {
+        self.build_child()?
+            .ok_or_else(|| self.error("Missing a child"))
+    }
+
+    pub fn children(&self) -> Vec<DescriptionNode<'a>> {
+        self.description
+            .children()
+            .iter()
+            .enumerate()
+            .map(|(index, child)| {
+                let path = format!("{}.children[{}]({})", self.path, index, child.widget_type());
+                DescriptionNode::new(child, self.registry, path)
+            })
+            .collect()
+    }
+
+    pub fn property(&self, name: &str) -> Option<&'a Value> {
+        self.description.properties().get(name).filter(|value| !value.is_null())
+    }
+
+    pub fn required<T>(&self, name: &str, value: Option<T>) -> Result<T, DescriptionError> {
+        value.ok_or_else(|| self.error(format!("Missing property `{}`", name)))
+    }
+
+    pub fn f32(&self, name: &str) -> Result<Option<f32>, DescriptionError> {
+        self.property(name)
+            .map(|value| {
+                value.as_f64()
+                    .map(|value| value as f32)
+                    .ok_or_else(|| self.invalid_property(name, "a number"))
+            })
+            .transpose()
+    }
+
+    pub fn u64(&self, name: &str) -> Result<Option<u64>, DescriptionError> {
+        self.property(name)
+            .map(|value| value.as_u64().ok_or_else(|| self.invalid_property(name, "an unsigned integer")))
+            .transpose()
+    }
+
+    pub fn bool(&self, name: &str) -> Result<Option<bool>, DescriptionError> {
+        self.property(name)
+            .map(|value| value.as_bool().ok_or_else(|| self.invalid_property(name, "a boolean")))
+            .transpose()
+    }
+
+    pub fn string(&self, name: &str) -> Result<Option<&'a str>, DescriptionError> {
+        self.property(name)
+            .map(|value| value.as_str().ok_or_else(|| self.invalid_property(name, "a string")))
+            .transpose()
+    }
+
+    pub fn strings(&self, name: &str) -> Result<Option<Vec<&'a str>>, DescriptionError> {
+        self.property(name)
+            .map(|value| {
+                value.as_array()
+                    .and_then(|values| values.iter().map(|value| value.as_str()).collect())
+                    .ok_or_else(|| self.invalid_property(name, "a list of strings"))
+            })
+            .transpose()
+    }
+
+    pub fn color(&self, name: &str) -> Result<Option<Color>, DescriptionError> {
+        self.property(name)
+            .map(|value| {
+                let color = match value {
+                    Value::Number(number) => number.as_u64().map(|argb| argb as u32),
+                    Value::String(hex) => parse_hex_color(hex),
+                    _ => None,
+                };
+                color
+                    .map(Color::new)
+                    .ok_or_else(|| self.invalid_property(name, "a color, e.g. \"#FF1E2429\""))
+            })
+            .transpose()
+    }
+
+    pub fn choice<T: Clone>(&self, name: &str, choices: &[(&str, T)]) -> Result<Option<T>, DescriptionError> {
+        let value = match self.string(name)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        choices
+            .iter()
+            .find(|(choice, _)| *choice == value)
+            .map(|(_, choice)| Some(choice.clone()))
+            .ok_or_else(|| {
+                let names = choices.iter().map(|(choice, _)| *choice).collect::<Vec<&str>>();
+                self.error(format!(
+                    "Property `{}` is `{}`, expected one of: {}",
+                    name,
+                    value,
+                    names.join(", "),
+                ))
+            })
+    }
+
+    fn invalid_property(&self, name: &str, expected: &str) -> DescriptionError {
+        self.error(format!("Property `{}` must be {}", name, expected))
+    }
+}
+
+fn parse_hex_color(hex: &str) -> Option<u32> {
+    let hex = hex.strip_prefix('#')?;
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    match hex.len() {
+        6 => Some(0xFF000000 | value),
+        8 => Some(value),
+        _ => None,
+    }
+}
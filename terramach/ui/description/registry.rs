@@ -0,0 +1,83 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::collections::HashMap;
+
+use crate::BoxedWidget;
+use crate::description::{builtin, DescriptionError, DescriptionNode, WidgetDescription};
+
+pub type WidgetFactory = Box<dyn Fn(&DescriptionNode) -> Result<BoxedWidget, DescriptionError>>;
+
+pub struct WidgetRegistry {
+    factories: HashMap<String, WidgetFactory>,
+}
+
+impl WidgetRegistry {
+    pub fn new() -> Self {
+        let mut registry = WidgetRegistry::new_empty();
+        builtin::register_widgets(&mut registry);
+        registry
+    }
+
+    pub fn new_empty() -> Self {
+        WidgetRegistry {
+            factories: HashMap::new(),
+        }
+    }
+
+    pub fn with_widget<F>(mut self, name: impl AsRef<str>, factory: F) -> Self
+        where F: 'static + Fn(&DescriptionNode) -> Result<BoxedWidget, DescriptionError> {
+        self.register(name, factory);
+        self
+    }
+
+    pub fn register<F>(&mut self, name: impl AsRef<str>, factory: F)
+        where F: 'static + Fn(&DescriptionNode) -> Result<BoxedWidget, DescriptionError> {
+        self.factories.insert(name.as_ref().to_string(), Box::new(factory));
+    }
+
+    pub fn contains(&self, name: impl AsRef<str>) -> bool {
+        self.factories.contains_key(name.as_ref())
+    }
+
+    pub fn build(&self, description: &WidgetDescription) -> Result<BoxedWidget, DescriptionError> {
+        let path = description.widget_type().to_string();
+        self.build_node(&DescriptionNode::new(description, self, path))
+    }
+
+    pub fn load_json(&self, json: impl AsRef<str>) -> Result<BoxedWidget, DescriptionError> {
+        self.build(&WidgetDescription::from_json(json)?)
+    }
+
+    pub fn load_ron(&self, ron: impl AsRef<str>) -> Result<BoxedWidget, DescriptionError> {
+        self.build(&WidgetDescription::from_ron(ron)?)
+    }
+
+    pub(crate) fn build_node(&self, node: &DescriptionNode) -> Result<BoxedWidget, DescriptionError> {
+        let factory = self.factories
+            .get(node.widget_type())
+            .ok_or_else(|| node.error(format!("Unknown widget type `{}`", node.widget_type())))?;
+        factory(node)
+    }
+}
+
+impl Default for WidgetRegistry {
+    fn default() -> Self {
+        WidgetRegistry::new()
+    }
+}
@@ -0,0 +1,80 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::any::Any;
+use std::fmt;
+
+use crate::Id;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum WidgetPhase {
+    Build,
+    Layout,
+    Paint,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct WidgetError {
+    widget: Id,
+    phase: WidgetPhase,
+    message: String,
+}
+
+impl WidgetError {
+    pub fn new(widget: Id, phase: WidgetPhase, message: impl AsRef<str>) -> Self {
+        WidgetError {
+            widget,
+            phase,
+            message: message.as_ref().to_string(),
+        }
+    }
+
+    pub(crate) fn from_panic(widget: Id, phase: WidgetPhase, payload: Box<dyn Any + Send>) -> Self {
+        WidgetError::new(widget, phase, panic_message(payload))
+    }
+
+    pub fn widget(&self) -> Id {
+        self.widget
+    }
+
+    pub fn phase(&self) -> WidgetPhase {
+        self.phase
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for WidgetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} failed: {}", self.phase, self.message)
+    }
+}
+
+pub type ErrorHandler = Box<dyn FnMut(&WidgetError)>;
+
+pub(crate) fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic".to_string()
+    }
+}
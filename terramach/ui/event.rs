@@ -16,10 +16,13 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
-use std::collections::VecDeque;
-use std::sync::{Arc, Mutex};
+use std::any::{type_name, Any};
+use std::collections::{HashSet, VecDeque};
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex, Weak};
 
-use crate::{Id, Touch, Touches, HitKey};
+use crate::{Id, Touch, Touches, HitKey, WidgetCallback, WidgetContext};
+use crate::platform::SharedRunLoop;
 
 use terramach_graphics::{Point, Size};
 
@@ -37,6 +40,7 @@ pub enum Event {
     Hover(Point),
     Enter,
     Leave,
+    /// Time elapsed on the render tree clock, see `RenderTree::with_clock`.
     Frame(TimePoint),
     Timer(Id),
     Tap(EventId),
@@ -44,6 +48,50 @@ pub enum Event {
     Focus(bool),
     BecameResponder,
     ResignedResponder,
+    Custom(CustomEvent),
+    Callback(WidgetCallback),
+}
+
+impl Event {
+    pub fn custom<T: Any>(&self) -> Option<&T> {
+        match self {
+            Event::Custom(event) => event.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CustomEvent {
+    payload: Arc<dyn Any + Send + Sync>,
+    type_name: &'static str,
+}
+
+impl CustomEvent {
+    pub fn new<T: Any + Send + Sync>(payload: T) -> Self {
+        CustomEvent {
+            payload: Arc::new(payload),
+            type_name: type_name::<T>(),
+        }
+    }
+
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    pub fn is<T: Any>(&self) -> bool {
+        self.payload.is::<T>()
+    }
+
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        self.payload.downcast_ref()
+    }
+}
+
+impl Debug for CustomEvent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CustomEvent").field(&self.type_name).finish()
+    }
 }
 
 pub type WidgetEvents = Events<Event>;
@@ -72,6 +120,10 @@ impl EventContext {
         &self.event
     }
 
+    pub fn custom<T: Any>(&self) -> Option<&T> {
+        self.event.custom()
+    }
+
     pub fn need_paint(&self) -> bool {
         self.need_paint
     }
@@ -179,3 +231,88 @@ impl<T> EventEmitter<T> {
         }
     }
 }
+
+impl EventEmitter<Event> {
+    pub fn emit_custom<T: Any + Send + Sync>(&mut self, payload: T) {
+        self.emit_event(Event::Custom(CustomEvent::new(payload)));
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct EventWakeup {
+    pending: Arc<Mutex<HashSet<Id>>>,
+    run_loop: Option<Arc<Mutex<SharedRunLoop>>>,
+}
+
+impl EventWakeup {
+    pub fn new(run_loop: impl Into<Option<SharedRunLoop>>) -> Self {
+        EventWakeup {
+            pending: Default::default(),
+            run_loop: run_loop.into().map(|run_loop| Arc::new(Mutex::new(run_loop))),
+        }
+    }
+
+    pub fn wakeup(&self, id: Id) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.insert(id);
+        }
+        if let Some(run_loop) = &self.run_loop {
+            if let Ok(mut run_loop) = run_loop.lock() {
+                run_loop.wakeup();
+            }
+        }
+    }
+
+    pub fn take_pending(&self) -> Vec<Id> {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.drain().collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WidgetEventSender {
+    widget: Id,
+    queue: Weak<Mutex<VecDeque<Event>>>,
+    wakeup: EventWakeup,
+}
+
+impl WidgetEventSender {
+    pub(crate) fn new(widget: Id, events: &WidgetEvents, wakeup: EventWakeup) -> Self {
+        WidgetEventSender {
+            widget,
+            queue: Arc::downgrade(&events.queue),
+            wakeup,
+        }
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.queue.strong_count() == 0
+    }
+
+    pub fn send(&self, event: Event) -> bool {
+        let queue = match self.queue.upgrade() {
+            Some(queue) => queue,
+            None => return false,
+        };
+        match queue.lock() {
+            Ok(mut queue) => queue.push_back(event),
+            Err(_) => return false,
+        }
+        self.wakeup.wakeup(self.widget);
+        true
+    }
+
+    pub fn send_custom<T: Any + Send + Sync>(&self, payload: T) -> bool {
+        self.send(Event::Custom(CustomEvent::new(payload)))
+    }
+
+    pub fn send_callback(
+        &self,
+        callback: impl FnOnce(&mut WidgetContext, &mut EventContext) + Send + 'static,
+    ) -> bool {
+        self.send(Event::Callback(WidgetCallback::new(callback)))
+    }
+}
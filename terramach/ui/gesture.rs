@@ -17,12 +17,15 @@
  */
 
 use std::ops::Sub;
+use std::time::{Duration, Instant};
 
 use crate::{Touches, TouchId};
 
 use terramach_graphics::Point;
 
 const MINIMUM_PAN_DISTANCE: f32 = 5.0;
+const DOUBLE_TAP_TIMEOUT: Duration = Duration::from_millis(300);
+const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
 
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum TapGestureState {
@@ -182,6 +185,133 @@ impl Clone for PanGesture {
     }
 }
 
+pub struct DoubleTapGesture {
+    tap: TapGesture,
+    timeout: Duration,
+    last_tap: Option<Instant>,
+}
+
+impl DoubleTapGesture {
+    pub fn new(timeout: Duration) -> Self {
+        DoubleTapGesture {
+            tap: TapGesture::default(),
+            timeout,
+            last_tap: None,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.tap.is_active() || self.last_tap.is_some()
+    }
+
+    pub fn update(&mut self, touches: &Touches, now: Instant) -> TapGestureState {
+        match self.tap.update(touches) {
+            TapGestureState::Ended => match self.last_tap.take() {
+                Some(last_tap) if now.duration_since(last_tap) <= self.timeout => TapGestureState::Ended,
+                _ => {
+                    self.last_tap = Some(now);
+                    TapGestureState::Changed
+                }
+            },
+            state => state,
+        }
+    }
+}
+
+impl Default for DoubleTapGesture {
+    fn default() -> Self {
+        DoubleTapGesture::new(DOUBLE_TAP_TIMEOUT)
+    }
+}
+
+impl Clone for DoubleTapGesture {
+    fn clone(&self) -> Self {
+        DoubleTapGesture::new(self.timeout)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum LongPressGestureState {
+    Possible,
+    Began,
+    Recognized,
+    Ended,
+}
+
+pub struct LongPressGesture {
+    duration: Duration,
+    start: Option<(Instant, Point)>,
+    state: LongPressGestureState,
+}
+
+impl LongPressGesture {
+    pub fn new(duration: Duration) -> Self {
+        LongPressGesture {
+            duration,
+            start: None,
+            state: LongPressGestureState::Possible,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.state == LongPressGestureState::Began
+    }
+
+    pub fn update(&mut self, touches: &Touches, now: Instant) -> LongPressGestureState {
+        if touches.is_empty() {
+            self.start = None;
+            self.state = LongPressGestureState::Possible;
+            return self.state;
+        }
+        let location = center_location(touches);
+        let state = match self.state {
+            LongPressGestureState::Possible => {
+                self.start = Some((now, location));
+                LongPressGestureState::Began
+            }
+            LongPressGestureState::Began => {
+                let (start_time, start_location) = self.start.unwrap_or((now, location));
+                let threshold = start_location.sub(location);
+                if threshold.x.abs() >= MINIMUM_PAN_DISTANCE || threshold.y.abs() >= MINIMUM_PAN_DISTANCE {
+                    LongPressGestureState::Ended
+                } else if now.duration_since(start_time) >= self.duration {
+                    LongPressGestureState::Recognized
+                } else {
+                    LongPressGestureState::Began
+                }
+            }
+            LongPressGestureState::Recognized | LongPressGestureState::Ended => LongPressGestureState::Ended,
+        };
+        self.state = state;
+        state
+    }
+
+    pub fn fire(&mut self, now: Instant) -> LongPressGestureState {
+        if let (LongPressGestureState::Began, Some((start_time, _))) = (self.state, self.start) {
+            if now.duration_since(start_time) >= self.duration {
+                self.state = LongPressGestureState::Recognized;
+            }
+        }
+        self.state
+    }
+}
+
+impl Default for LongPressGesture {
+    fn default() -> Self {
+        LongPressGesture::new(LONG_PRESS_DURATION)
+    }
+}
+
+impl Clone for LongPressGesture {
+    fn clone(&self) -> Self {
+        LongPressGesture::new(self.duration)
+    }
+}
+
 fn center_location(touches: &Touches) -> Point {
     let touches_count = touches.len();
     if touches_count == 0 {
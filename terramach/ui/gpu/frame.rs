@@ -16,23 +16,35 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
-use terramach_graphics::{Canvas, Size};
+use terramach_graphics::Canvas;
 
 use crate::LayerTree;
-use crate::gpu::TextureRegistry;
+use crate::gpu::{FrameTimings, RasterCache, TextureRegistry};
 
 pub struct Frame {
     layers: LayerTree,
+    timings: Option<FrameTimings>,
 }
 
 impl Frame {
     pub fn new(layers: &LayerTree) -> Self {
         Frame {
             layers: layers.clone(),
+            timings: None,
         }
     }
 
-    pub fn draw(&self, canvas: &mut Canvas, size: impl Into<Size>, textures: &mut TextureRegistry) {
-        self.layers.draw(canvas, size, textures);
+    pub fn with_timings(mut self, timings: impl Into<Option<FrameTimings>>) -> Self {
+        self.timings = timings.into();
+        self
+    }
+
+    pub fn timings(&self) -> Option<&FrameTimings> {
+        self.timings.as_ref()
+    }
+
+    pub fn draw(&self, canvas: &mut Canvas, textures: &mut TextureRegistry, raster_cache: &mut RasterCache) {
+        self.layers.draw(canvas, textures, raster_cache);
+        raster_cache.sweep();
     }
 }
@@ -0,0 +1,152 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::time::{Duration, Instant};
+
+pub type FrameTimingsHandler = Box<dyn FnMut(&FrameTimings) + Send>;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FrameTimings {
+    frame: u64,
+    build: Duration,
+    layout: Duration,
+    paint: Duration,
+    frame_clone: Duration,
+    raster: Duration,
+    present: Duration,
+    widgets_built: usize,
+    widgets_laid_out: usize,
+    widgets_painted: usize,
+}
+
+impl FrameTimings {
+    pub(crate) fn new(frame: u64) -> Self {
+        FrameTimings {
+            frame,
+            ..Default::default()
+        }
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn build(&self) -> Duration {
+        self.build
+    }
+
+    pub fn layout(&self) -> Duration {
+        self.layout
+    }
+
+    pub fn paint(&self) -> Duration {
+        self.paint
+    }
+
+    pub fn frame_clone(&self) -> Duration {
+        self.frame_clone
+    }
+
+    pub fn raster(&self) -> Duration {
+        self.raster
+    }
+
+    pub fn present(&self) -> Duration {
+        self.present
+    }
+
+    pub fn ui_thread(&self) -> Duration {
+        self.build + self.layout + self.paint + self.frame_clone
+    }
+
+    pub fn raster_thread(&self) -> Duration {
+        self.raster + self.present
+    }
+
+    pub fn total(&self) -> Duration {
+        self.ui_thread() + self.raster_thread()
+    }
+
+    pub fn widgets_built(&self) -> usize {
+        self.widgets_built
+    }
+
+    pub fn widgets_laid_out(&self) -> usize {
+        self.widgets_laid_out
+    }
+
+    pub fn widgets_painted(&self) -> usize {
+        self.widgets_painted
+    }
+
+    pub(crate) fn add_build(&mut self, duration: Duration) {
+        self.build += duration;
+    }
+
+    pub(crate) fn add_layout(&mut self, duration: Duration) {
+        self.layout += duration;
+    }
+
+    pub(crate) fn add_paint(&mut self, duration: Duration) {
+        self.paint += duration;
+    }
+
+    pub(crate) fn set_frame_clone(&mut self, duration: Duration) {
+        self.frame_clone = duration;
+    }
+
+    pub(crate) fn set_raster(&mut self, duration: Duration) {
+        self.raster = duration;
+    }
+
+    pub(crate) fn set_present(&mut self, duration: Duration) {
+        self.present = duration;
+    }
+
+    pub(crate) fn add_widgets_built(&mut self, count: usize) {
+        self.widgets_built += count;
+    }
+
+    pub(crate) fn add_widgets_laid_out(&mut self, count: usize) {
+        self.widgets_laid_out += count;
+    }
+
+    pub(crate) fn add_widgets_painted(&mut self, count: usize) {
+        self.widgets_painted += count;
+    }
+}
+
+pub(crate) struct FrameSpan {
+    start: Instant,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl FrameSpan {
+    pub fn enter(phase: &'static str) -> Self {
+        FrameSpan {
+            start: Instant::now(),
+            #[cfg(feature = "tracing")]
+            _span: tracing::trace_span!("frame", phase).entered(),
+        }
+    }
+
+    pub fn finish(self) -> Duration {
+        self.start.elapsed()
+    }
+}
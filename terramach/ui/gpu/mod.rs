@@ -17,11 +17,17 @@
  */
 
 mod frame;
+mod frame_timings;
+mod performance_stats;
 mod pipeline;
+mod raster_cache;
 mod texture;
 mod gl_texture;
 
 pub use frame::*;
+pub use frame_timings::*;
+pub use performance_stats::*;
 pub use pipeline::*;
+pub use raster_cache::*;
 pub use texture::*;
 pub use gl_texture::*;
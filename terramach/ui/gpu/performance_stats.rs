@@ -0,0 +1,70 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::gpu::FrameTimings;
+
+#[derive(Clone, Default)]
+pub struct PerformanceStats {
+    samples: Arc<Mutex<VecDeque<(Instant, FrameTimings)>>>,
+}
+
+impl PerformanceStats {
+    pub const CAPACITY: usize = 120;
+
+    pub fn new() -> Self {
+        PerformanceStats::default()
+    }
+
+    pub(crate) fn record(&self, timings: FrameTimings) {
+        if let Ok(mut samples) = self.samples.lock() {
+            if samples.len() == PerformanceStats::CAPACITY {
+                samples.pop_front();
+            }
+            samples.push_back((Instant::now(), timings));
+        }
+    }
+
+    pub fn timings(&self) -> Vec<FrameTimings> {
+        self.samples
+            .lock()
+            .map(|samples| samples.iter().map(|(_, timings)| timings.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn frames_per_second(&self) -> f32 {
+        let samples = match self.samples.lock() {
+            Ok(samples) => samples,
+            Err(_) => return 0.0,
+        };
+        match (samples.front(), samples.back()) {
+            (Some((first, _)), Some((last, _))) if samples.len() > 1 => {
+                let elapsed = last.duration_since(*first).as_secs_f32();
+                if elapsed > 0.0 {
+                    (samples.len() - 1) as f32 / elapsed
+                } else {
+                    0.0
+                }
+            }
+            _ => 0.0,
+        }
+    }
+}
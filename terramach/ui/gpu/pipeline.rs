@@ -20,7 +20,7 @@ use std::sync::mpsc::{channel, Receiver, Sender, TryRecvError};
 
 use terramach_graphics::{Display, Size};
 
-use crate::gpu::{Frame, TextureRegistry, RenderTexture, TextureId, Texture};
+use crate::gpu::{Frame, FrameSpan, FrameTimingsHandler, PerformanceStats, TextureRegistry, RenderTexture, TextureId, Texture, RasterCache};
 use crate::platform::VSync;
 use crate::Id;
 
@@ -36,19 +36,27 @@ enum Command {
 
 pub struct Pipeline {
     sender: Sender<Command>,
+    stats: PerformanceStats,
 }
 
 impl Pipeline {
-    pub fn new(vsync: VSync, display: Box<dyn Display>) -> Self {
+    pub fn new(
+        vsync: VSync,
+        display: Box<dyn Display>,
+        frame_timings: impl Into<Option<FrameTimingsHandler>>,
+    ) -> Self {
         let (sender, receiver) = channel();
+        let stats = PerformanceStats::new();
         let mut render_pipeline = RenderPipeline::new(
             vsync,
             display,
             sender.clone(),
             receiver,
+            stats.clone(),
+            frame_timings.into(),
         );
         std::thread::spawn(move || render_pipeline.render());
-        Pipeline { sender }
+        Pipeline { sender, stats }
     }
 
     pub fn resize(&mut self, display_size: impl Into<Size>) {
@@ -56,7 +64,7 @@ impl Pipeline {
     }
 
     pub fn share(&self) -> SharedPipeline {
-        SharedPipeline::new(&self.sender)
+        SharedPipeline::new(&self.sender, &self.stats)
     }
 }
 
@@ -69,15 +77,21 @@ impl Drop for Pipeline {
 #[derive(Clone)]
 pub struct SharedPipeline {
     sender: Sender<Command>,
+    stats: PerformanceStats,
 }
 
 impl SharedPipeline {
-    fn new(sender: &Sender<Command>) -> Self {
+    fn new(sender: &Sender<Command>, stats: &PerformanceStats) -> Self {
         SharedPipeline {
             sender: sender.clone(),
+            stats: stats.clone(),
         }
     }
 
+    pub fn performance_stats(&self) -> PerformanceStats {
+        self.stats.clone()
+    }
+
     pub fn submit_frame(&mut self, frame: Frame) {
         let _ = self.sender.send(Command::Push(frame));
     }
@@ -99,11 +113,46 @@ impl SharedPipeline {
     }
 }
 
+#[cfg(feature = "testing")]
+pub(crate) struct DetachedPipeline {
+    sender: Sender<Command>,
+    receiver: Receiver<Command>,
+    stats: PerformanceStats,
+}
+
+#[cfg(feature = "testing")]
+impl DetachedPipeline {
+    pub fn new() -> Self {
+        let (sender, receiver) = channel();
+        DetachedPipeline {
+            sender,
+            receiver,
+            stats: PerformanceStats::new(),
+        }
+    }
+
+    pub fn share(&self) -> SharedPipeline {
+        SharedPipeline::new(&self.sender, &self.stats)
+    }
+
+    pub fn poll_frame(&mut self) -> Option<Frame> {
+        let mut frame = None;
+        while let Ok(command) = self.receiver.try_recv() {
+            if let Command::Push(new_frame) = command {
+                frame = Some(new_frame);
+            }
+        }
+        frame
+    }
+}
+
 struct RenderPipeline {
     vsync: VSync,
     display: Box<dyn Display>,
     sender: Sender<Command>,
     receiver: Receiver<Command>,
+    stats: PerformanceStats,
+    frame_timings: Option<FrameTimingsHandler>,
 }
 
 impl RenderPipeline {
@@ -112,20 +161,26 @@ impl RenderPipeline {
         display: Box<dyn Display>,
         sender: Sender<Command>,
         receiver: Receiver<Command>,
+        stats: PerformanceStats,
+        frame_timings: Option<FrameTimingsHandler>,
     ) -> Self {
         RenderPipeline {
             vsync,
             display,
             sender,
             receiver,
+            stats,
+            frame_timings,
         }
     }
 
     pub fn render(&mut self) -> Option<()> {
         self.display.make_current();
         let mut surface = self.display.new_surface()?;
-        let mut frame = None;
+        let mut frame: Option<Frame> = None;
+        let mut timings = None;
         let mut textures = TextureRegistry::new();
+        let mut raster_cache = RasterCache::new();
 
         'main: while let Ok(command) = self.receiver.recv() {
             let mut will_draw_frame = false;
@@ -147,6 +202,7 @@ impl RenderPipeline {
                     Command::Terminate => break,
                     Command::Push(new_frame) => {
                         will_draw_frame = true;
+                        timings = new_frame.timings().cloned();
                         frame = Some(new_frame);
                     }
                     Command::Resize(size) => {
@@ -164,7 +220,7 @@ impl RenderPipeline {
                                     pixel_ratio,
                                     context,
                                     id,
-                                    SharedPipeline::new(&self.sender),
+                                    SharedPipeline::new(&self.sender, &self.stats),
                                     render_texture,
                                 );
                                 textures.register(id, texture);
@@ -196,11 +252,25 @@ impl RenderPipeline {
 
             if will_draw_frame {
                 if let Some(frame) = &frame {
+                    let raster = FrameSpan::enter("raster");
                     let canvas = surface.canvas();
                     canvas.clear(0);
-                    frame.draw(canvas, self.display.size(), &mut textures);
+                    frame.draw(canvas, &mut textures, &mut raster_cache);
                     canvas.flush();
+                    let raster = raster.finish();
+
+                    let present = FrameSpan::enter("present");
                     self.display.present_current();
+                    let present = present.finish();
+
+                    if let Some(mut timings) = timings.take() {
+                        timings.set_raster(raster);
+                        timings.set_present(present);
+                        if let Some(frame_timings) = &mut self.frame_timings {
+                            frame_timings(&timings);
+                        }
+                        self.stats.record(timings);
+                    }
                 }
             }
         }
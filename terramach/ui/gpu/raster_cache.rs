@@ -0,0 +1,147 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::collections::{HashMap, HashSet};
+
+use terramach_graphics::{Image, Size};
+
+use crate::Id;
+
+const MAX_ENTRY_DIMENSION: i32 = 4096;
+const DEFAULT_BUDGET: usize = 64 * 1024 * 1024;
+const BYTES_PER_PIXEL: usize = 4;
+
+struct RasterCacheEntry {
+    version: u64,
+    size: Size,
+    scale: (f32, f32),
+    image: Image,
+}
+
+impl RasterCacheEntry {
+    fn bytes(&self) -> usize {
+        image_bytes(self.image.width(), self.image.height())
+    }
+}
+
+pub struct RasterCache {
+    entries: HashMap<Id, RasterCacheEntry>,
+    used: HashSet<Id>,
+    budget: usize,
+}
+
+impl RasterCache {
+    pub fn new() -> Self {
+        RasterCache {
+            entries: HashMap::new(),
+            used: HashSet::new(),
+            budget: DEFAULT_BUDGET,
+        }
+    }
+
+    pub fn with_budget(mut self, budget: usize) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    pub fn used_bytes(&self) -> usize {
+        self.entries.values().map(|entry| entry.bytes()).sum()
+    }
+
+    pub fn fits(&self, id: Id, width: i32, height: i32) -> bool {
+        if width > MAX_ENTRY_DIMENSION || height > MAX_ENTRY_DIMENSION {
+            return false;
+        }
+        let retained: usize = self
+            .entries
+            .iter()
+            .filter(|(entry_id, _)| **entry_id != id && self.used.contains(entry_id))
+            .map(|(_, entry)| entry.bytes())
+            .sum();
+        retained + image_bytes(width, height) <= self.budget
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn image(&mut self, id: Id, version: u64, size: Size, scale: (f32, f32)) -> Option<&Image> {
+        let entry = self.entries.get(&id)?;
+        if entry.version != version || entry.size != size || entry.scale != scale {
+            return None;
+        }
+        self.used.insert(id);
+        Some(&entry.image)
+    }
+
+    pub fn insert(&mut self, id: Id, version: u64, size: Size, scale: (f32, f32), image: Image) {
+        self.used.insert(id);
+        self.entries.insert(id, RasterCacheEntry {
+            version,
+            size,
+            scale,
+            image,
+        });
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        let mut used_bytes = self.used_bytes();
+        if used_bytes <= self.budget {
+            return;
+        }
+        let unused: Vec<Id> = self
+            .entries
+            .keys()
+            .filter(|id| !self.used.contains(id))
+            .cloned()
+            .collect();
+        for id in unused {
+            if used_bytes <= self.budget {
+                break;
+            }
+            if let Some(entry) = self.entries.remove(&id) {
+                used_bytes -= entry.bytes();
+            }
+        }
+    }
+
+    pub fn sweep(&mut self) {
+        let used = &self.used;
+        self.entries.retain(|id, _| used.contains(id));
+        self.used.clear();
+    }
+}
+
+impl Default for RasterCache {
+    fn default() -> Self {
+        RasterCache::new()
+    }
+}
+
+fn image_bytes(width: i32, height: i32) -> usize {
+    width.max(0) as usize * height.max(0) as usize * BYTES_PER_PIXEL
+}
@@ -0,0 +1,89 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::fmt::{Debug, Formatter};
+use std::sync::{Arc, Mutex};
+
+use crate::{EventContext, WidgetContext, WidgetEventSender};
+
+type Callback = Box<dyn FnOnce(&mut WidgetContext, &mut EventContext) + Send>;
+
+#[derive(Clone)]
+pub struct WidgetCallback {
+    callback: Arc<Mutex<Option<Callback>>>,
+}
+
+impl WidgetCallback {
+    pub fn new(callback: impl FnOnce(&mut WidgetContext, &mut EventContext) + Send + 'static) -> Self {
+        WidgetCallback {
+            callback: Arc::new(Mutex::new(Some(Box::new(callback)))),
+        }
+    }
+
+    pub(crate) fn invoke(&self, context: &mut WidgetContext, event: &mut EventContext) {
+        let callback = self.callback.lock().ok().and_then(|mut callback| callback.take());
+        if let Some(callback) = callback {
+            callback(context, event);
+        }
+    }
+}
+
+impl Debug for WidgetCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("WidgetCallback")
+    }
+}
+
+#[derive(Clone)]
+pub struct WidgetHandle {
+    event_sender: WidgetEventSender,
+}
+
+impl WidgetHandle {
+    pub fn new(event_sender: WidgetEventSender) -> Self {
+        WidgetHandle { event_sender }
+    }
+
+    pub fn is_mounted(&self) -> bool {
+        !self.event_sender.is_closed()
+    }
+
+    pub fn update(&self, callback: impl FnOnce(&mut WidgetContext, &mut EventContext) + Send + 'static) {
+        self.event_sender.send_callback(callback);
+    }
+
+    pub fn update_state<T: 'static>(&self, callback: impl FnOnce(&mut T, &mut EventContext) + Send + 'static) {
+        self.update(|context, event| {
+            if let Some(state) = context.state_mut::<T>() {
+                callback(state, event);
+            }
+        });
+    }
+
+    pub fn mark_need_build(&self) {
+        self.update(|_, event| event.mark_need_build());
+    }
+
+    pub fn mark_need_layout(&self) {
+        self.update(|_, event| event.mark_need_layout());
+    }
+
+    pub fn mark_need_paint(&self) {
+        self.update(|_, event| event.mark_need_paint());
+    }
+}
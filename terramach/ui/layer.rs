@@ -16,20 +16,20 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
+use std::sync::Arc;
+
 use crate::DrawContext;
 
 use terramach_graphics::Display;
 
 pub type BoxedLayer = Box<dyn Layer>;
 
-pub trait Layer: Send {
-    fn draw(&self, draw: &mut DrawContext);
+pub type SharedLayer = Arc<dyn Layer>;
 
-    fn clone_boxed(&self) -> BoxedLayer;
-}
+pub trait Layer: Send + Sync {
+    fn draw(&self, draw: &mut DrawContext);
 
-impl Clone for BoxedLayer {
-    fn clone(&self) -> Self {
-        self.clone_boxed()
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
     }
 }
@@ -16,7 +16,7 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
-use crate::{BoxedLayer, DrawContext, Layer};
+use crate::{DrawContext, Layer};
 
 use terramach_graphics::Rect;
 
@@ -42,8 +42,4 @@ impl Layer for ClipRectLayer {
         let canvas = draw.canvas();
         canvas.restore_to_count(count);
     }
-
-    fn clone_boxed(&self) -> BoxedLayer {
-        Box::new(self.clone())
-    }
 }
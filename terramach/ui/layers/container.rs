@@ -20,7 +20,6 @@ use crate::{BoxedLayer, DrawContext, Layer};
 
 use terramach_graphics::{Canvas, Size};
 
-#[derive(Clone)]
 pub struct ContainerLayer {
     layers: Vec<BoxedLayer>,
 }
@@ -44,8 +43,4 @@ impl Layer for ContainerLayer {
     fn draw(&self, draw: &mut DrawContext) {
         draw.draw_children_with_layers(&self.layers);
     }
-
-    fn clone_boxed(&self) -> BoxedLayer {
-        Box::new(self.clone())
-    }
 }
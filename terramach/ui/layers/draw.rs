@@ -16,17 +16,18 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
-use terramach_graphics::{Canvas, Size};
+use terramach_graphics::{Canvas, ImageInfo, Size};
 use terramach_graphics::gpu;
 
-use crate::gpu::{TextureRegistry, TextureId};
-use crate::BoxedLayer;
+use crate::gpu::{RasterCache, TextureRegistry, TextureId};
+use crate::{BoxedLayer, Id};
 
 pub struct DrawContext<'a> {
     size: Size,
     canvas: &'a mut Canvas,
     textures: &'a mut TextureRegistry,
-    draw_children: &'a mut dyn FnMut(&mut Canvas, &mut TextureRegistry),
+    raster_cache: &'a mut RasterCache,
+    draw_children: &'a mut dyn FnMut(&mut Canvas, &mut TextureRegistry, &mut RasterCache),
     draw_layers: Option<&'a [&'a BoxedLayer]>,
 }
 
@@ -35,12 +36,14 @@ impl<'a> DrawContext<'a> {
         size: impl Into<Size>,
         canvas: &'a mut Canvas,
         textures: &'a mut TextureRegistry,
+        raster_cache: &'a mut RasterCache,
         draw_children: &'a mut F,
-    ) -> Self where F: FnMut(&mut Canvas, &mut TextureRegistry) {
+    ) -> Self where F: FnMut(&mut Canvas, &mut TextureRegistry, &mut RasterCache) {
         DrawContext {
             size: size.into(),
             canvas,
             textures,
+            raster_cache,
             draw_children,
             draw_layers: None,
         }
@@ -68,6 +71,7 @@ impl<'a> DrawContext<'a> {
                     size: self.size,
                     canvas: self.canvas,
                     textures: self.textures,
+                    raster_cache: self.raster_cache,
                     draw_children: self.draw_children,
                     draw_layers: Some(&layers[1..]),
                 };
@@ -75,7 +79,50 @@ impl<'a> DrawContext<'a> {
                 return;
             }
         }
-        (self.draw_children)(self.canvas, self.textures);
+        (self.draw_children)(self.canvas, self.textures, self.raster_cache);
+    }
+
+    pub fn draw_children_cached(&mut self, id: Id, version: u64) {
+        let matrix = self.canvas.total_matrix();
+        let scale = (matrix.scale_x(), matrix.scale_y());
+        if self.raster_cache.image(id, version, self.size, scale).is_none() {
+            let width = (self.size.width * scale.0).ceil() as i32;
+            let height = (self.size.height * scale.1).ceil() as i32;
+            if width <= 0 || height <= 0 {
+                return;
+            }
+            if !self.raster_cache.fits(id, width, height) {
+                self.draw_children();
+                return;
+            }
+            let info = ImageInfo::new_n32_premul((width, height), None);
+            let mut surface = if let Some(surface) = self.canvas.new_surface(&info, None) {
+                surface
+            } else {
+                self.draw_children();
+                return;
+            };
+            let canvas = surface.canvas();
+            canvas.clear(0);
+            canvas.scale(scale);
+            let mut context = DrawContext {
+                size: self.size,
+                canvas,
+                textures: self.textures,
+                raster_cache: self.raster_cache,
+                draw_children: self.draw_children,
+                draw_layers: self.draw_layers,
+            };
+            context.draw_children();
+            let image = surface.image_snapshot();
+            self.raster_cache.insert(id, version, self.size, scale, image);
+        }
+        if let Some(image) = self.raster_cache.image(id, version, self.size, scale) {
+            let count = self.canvas.save();
+            self.canvas.scale((1.0 / scale.0, 1.0 / scale.1));
+            self.canvas.draw_image(image, (0.0, 0.0), None);
+            self.canvas.restore_to_count(count);
+        }
     }
 
     pub fn draw_children_with_layers(&mut self, layers: &Vec<BoxedLayer>) {
@@ -88,6 +135,7 @@ impl<'a> DrawContext<'a> {
             size: self.size,
             canvas: self.canvas,
             textures: self.textures,
+            raster_cache: self.raster_cache,
             draw_children: self.draw_children,
             draw_layers: Some(&draw_layers[..]),
         };
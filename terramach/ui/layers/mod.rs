@@ -22,7 +22,9 @@ mod container;
 mod draw;
 mod offset;
 mod opacity;
+mod performance_overlay;
 mod picture;
+mod repaint_boundary;
 mod tree;
 mod texture;
 
@@ -32,6 +34,8 @@ pub use container::*;
 pub use draw::*;
 pub use offset::*;
 pub use opacity::*;
+pub use performance_overlay::*;
 pub use picture::*;
+pub use repaint_boundary::*;
 pub use tree::*;
 pub use texture::*;
@@ -0,0 +1,114 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::time::Duration;
+
+use crate::{DrawContext, Layer};
+use crate::gpu::PerformanceStats;
+
+use terramach_graphics::{Canvas, Color, Color4f, Font, Paint, Point, Rect};
+
+const FRAME_BUDGET: Duration = Duration::from_nanos(16_666_667);
+const BACKGROUND_COLOR: Color = Color::new(0xC0000000);
+const TEXT_COLOR: Color = Color::new(0xFFFFFFFF);
+const BUDGET_COLOR: Color = Color::new(0x80FFFFFF);
+const UI_COLOR: Color = Color::new(0xFF4CAF50);
+const RASTER_COLOR: Color = Color::new(0xFF2196F3);
+const OVER_BUDGET_COLOR: Color = Color::new(0xFFF44336);
+const TEXT_HEIGHT: f32 = 16.0;
+const TEXT_SIZE: f32 = 11.0;
+
+#[derive(Clone)]
+pub struct PerformanceOverlayLayer {
+    stats: PerformanceStats,
+    rect: Rect,
+}
+
+impl PerformanceOverlayLayer {
+    pub fn new(stats: PerformanceStats, rect: impl Into<Rect>) -> Self {
+        PerformanceOverlayLayer {
+            stats,
+            rect: rect.into(),
+        }
+    }
+
+    fn draw_graph(&self, canvas: &mut Canvas, rect: Rect, durations: &[Duration], color: Color) {
+        let bar_width = rect.width() / PerformanceStats::CAPACITY as f32;
+        let budget = FRAME_BUDGET.as_secs_f32();
+        let bar = Paint::new(Color4f::from(color), None);
+        let over_budget = Paint::new(Color4f::from(OVER_BUDGET_COLOR), None);
+        for (index, duration) in durations.iter().enumerate() {
+            let duration = duration.as_secs_f32();
+            let height = (duration / (budget * 2.0)).min(1.0) * rect.height();
+            let left = rect.left + index as f32 * bar_width;
+            canvas.draw_rect(
+                Rect::new(left, rect.bottom - height, left + bar_width, rect.bottom),
+                if duration > budget { &over_budget } else { &bar },
+            );
+        }
+        let budget_line = rect.bottom - rect.height() / 2.0;
+        canvas.draw_line(
+            Point::new(rect.left, budget_line),
+            Point::new(rect.right, budget_line),
+            &Paint::new(Color4f::from(BUDGET_COLOR), None),
+        );
+    }
+}
+
+impl Layer for PerformanceOverlayLayer {
+    fn draw(&self, draw: &mut DrawContext) {
+        let timings = self.stats.timings();
+        let ui_thread = timings.iter().map(|timings| timings.ui_thread()).collect::<Vec<Duration>>();
+        let raster_thread = timings.iter().map(|timings| timings.raster_thread()).collect::<Vec<Duration>>();
+
+        let canvas = draw.canvas();
+        let count = canvas.save();
+        canvas.clip_rect(self.rect, None, None);
+        canvas.draw_rect(self.rect, &Paint::new(Color4f::from(BACKGROUND_COLOR), None));
+
+        let graph_height = (self.rect.height() - TEXT_HEIGHT) / 2.0;
+        let ui_rect = Rect::new(
+            self.rect.left,
+            self.rect.top + TEXT_HEIGHT,
+            self.rect.right,
+            self.rect.top + TEXT_HEIGHT + graph_height,
+        );
+        let raster_rect = Rect::new(self.rect.left, ui_rect.bottom, self.rect.right, self.rect.bottom);
+        self.draw_graph(canvas, ui_rect, &ui_thread, UI_COLOR);
+        self.draw_graph(canvas, raster_rect, &raster_thread, RASTER_COLOR);
+
+        let last_ms = |durations: &[Duration]| {
+            durations.last().map_or(0.0, |duration| duration.as_secs_f32() * 1000.0)
+        };
+        let text = format!(
+            "{:.0} fps  ui {:.1} ms  raster {:.1} ms",
+            self.stats.frames_per_second(),
+            last_ms(&ui_thread),
+            last_ms(&raster_thread),
+        );
+        let mut font = Font::default();
+        font.set_size(TEXT_SIZE);
+        canvas.draw_str(
+            text,
+            Point::new(self.rect.left + 4.0, self.rect.top + TEXT_HEIGHT - 4.0),
+            &font,
+            &Paint::new(Color4f::from(TEXT_COLOR), None),
+        );
+        canvas.restore_to_count(count);
+    }
+}
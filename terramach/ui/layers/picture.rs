@@ -18,7 +18,7 @@
 
 use std::sync::Arc;
 
-use crate::{BoxedLayer, DrawContext, Layer};
+use crate::{DrawContext, Layer};
 
 use terramach_graphics::Picture;
 
@@ -29,6 +29,8 @@ pub struct PictureLayer {
 
 unsafe impl Send for PictureLayer {}
 
+unsafe impl Sync for PictureLayer {}
+
 impl PictureLayer {
     pub fn new(picture: Picture) -> Self {
         PictureLayer {
@@ -42,8 +44,4 @@ impl Layer for PictureLayer {
         self.picture.playback(draw.canvas());
         draw.draw_children();
     }
-
-    fn clone_boxed(&self) -> BoxedLayer {
-        Box::new(self.clone())
-    }
 }
@@ -0,0 +1,46 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use crate::{DrawContext, Id, Layer};
+
+#[derive(Clone)]
+pub struct RepaintBoundaryLayer {
+    id: Id,
+    version: u64,
+    cacheable: bool,
+}
+
+impl RepaintBoundaryLayer {
+    pub fn new(id: Id, version: u64, cacheable: bool) -> Self {
+        RepaintBoundaryLayer {
+            id,
+            version,
+            cacheable,
+        }
+    }
+}
+
+impl Layer for RepaintBoundaryLayer {
+    fn draw(&self, draw: &mut DrawContext) {
+        if self.cacheable {
+            draw.draw_children_cached(self.id, self.version);
+        } else {
+            draw.draw_children();
+        }
+    }
+}
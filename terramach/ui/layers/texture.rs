@@ -17,7 +17,7 @@
  */
 
 use crate::gpu::TextureId;
-use crate::{Layer, DrawContext, BoxedLayer};
+use crate::{Layer, DrawContext};
 
 #[derive(Clone)]
 pub struct TextureLayer {
@@ -37,8 +37,4 @@ impl Layer for TextureLayer {
         draw.draw_texture(self.texture_id);
         draw.draw_children();
     }
-
-    fn clone_boxed(&self) -> BoxedLayer {
-        Box::new(self.clone())
-    }
 }
@@ -16,152 +16,110 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
-use std::collections::HashMap;
+use std::sync::Arc;
 
-use terramach_graphics::{Canvas, Picture, PictureRecorder, Rect, Size, Display};
-use terramach_graphics::gpu;
+#[cfg(feature = "inspector")]
+use serde_json::{json, Value};
 
-use crate::{BoxedLayer, DrawContext, Id, Tree};
-use crate::gpu::TextureRegistry;
+use terramach_graphics::{Canvas, Size};
 
-#[derive(Clone)]
-struct LayerKey {
+use crate::{DrawContext, SharedLayer};
+#[cfg(feature = "inspector")]
+use crate::inspect_size;
+use crate::gpu::{RasterCache, TextureRegistry};
+
+pub type SharedLayerNode = Arc<LayerNode>;
+
+pub struct LayerNode {
+    layer: SharedLayer,
     size: Size,
-    layers: Vec<Id>,
-    leaf_layers: Vec<Id>,
+    children: Vec<SharedLayerNode>,
 }
 
-impl LayerKey {
-    fn new(
+impl LayerNode {
+    pub fn new(
+        layer: SharedLayer,
         size: impl Into<Size>,
-        layers: impl Into<Option<Vec<Id>>>,
-        leaf_layers: impl Into<Option<Vec<Id>>>,
-    ) -> Self {
-        LayerKey {
+        children: impl Into<Option<Vec<SharedLayerNode>>>,
+    ) -> SharedLayerNode {
+        Arc::new(LayerNode {
+            layer,
             size: size.into(),
-            layers: layers.into().unwrap_or_default(),
-            leaf_layers: leaf_layers.into().unwrap_or_default(),
-        }
+            children: children.into().unwrap_or_default(),
+        })
     }
-}
-
-#[derive(Clone)]
-pub struct LayerTree {
-    tree: Tree<BoxedLayer>,
-    keys: HashMap<Id, LayerKey>,
-    layer_key: HashMap<Id, Id>,
-}
 
-impl LayerTree {
-    pub fn new() -> Self {
-        LayerTree {
-            tree: Tree::new(),
-            keys: HashMap::new(),
-            layer_key: HashMap::new(),
-        }
+    pub fn layer(&self) -> &SharedLayer {
+        &self.layer
     }
 
-    pub fn insert(
-        &mut self,
-        key: Id,
-        size: impl Into<Size>,
-        layer: BoxedLayer,
-        parent: impl Into<Option<Id>>,
-    ) -> Id {
-        let id = self.tree.insert(layer, parent);
-        if let Some(key) = self.keys.get_mut(&key) {
-            key.layers.push(id);
-        } else {
-            self.keys.insert(key, LayerKey::new(size, vec![id], None));
-        }
-        self.layer_key.insert(id, key);
-        id
+    pub fn size(&self) -> Size {
+        self.size
     }
 
-    pub fn insert_leaf(
-        &mut self,
-        key: Id,
-        size: impl Into<Size>,
-        layer: BoxedLayer,
-        parent: impl Into<Option<Id>>,
-    ) -> Id {
-        let id = self.tree.insert(layer, parent);
-        if let Some(key) = self.keys.get_mut(&key) {
-            key.leaf_layers.push(id);
-        } else {
-            self.keys.insert(key, LayerKey::new(size, None, vec![id]));
-        }
-        self.layer_key.insert(id, key);
-        id
+    pub fn children(&self) -> &Vec<SharedLayerNode> {
+        &self.children
     }
 
-    pub fn key_layers(&self, key: Id) -> Option<&Vec<Id>> {
-        Some(&self.keys.get(&key)?.layers)
+    #[cfg(feature = "inspector")]
+    pub fn inspect(&self) -> Value {
+        json!({
+            "type": self.layer.type_name(),
+            "size": inspect_size(self.size),
+            "children": self.children.iter().map(|child| child.inspect()).collect::<Vec<Value>>(),
+        })
     }
 
-    pub fn drop_key_layer(&mut self, key: Id) {
-        if let Some(key) = self.keys.remove(&key) {
-            for id in &key.layers {
-                self.layer_key.remove(id);
-                if let Some(removed) = self.tree.remove_all(*id) {
-                    for id in removed {
-                        if let Some(key) = self.layer_key.remove(&id) {
-                            self.drop_key_layer(key);
-                        }
-                    }
-                }
+    fn draw(&self, canvas: &mut Canvas, textures: &mut TextureRegistry, raster_cache: &mut RasterCache) {
+        let mut draw_children = move |canvas: &mut Canvas,
+                                      textures: &mut TextureRegistry,
+                                      raster_cache: &mut RasterCache| {
+            for child in &self.children {
+                child.draw(canvas, textures, raster_cache);
             }
+        };
+        self.layer.draw(&mut DrawContext::new(
+            self.size,
+            canvas,
+            textures,
+            raster_cache,
+            &mut draw_children,
+        ));
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct LayerTree {
+    nodes: Vec<SharedLayerNode>,
+}
+
+impl LayerTree {
+    pub fn new() -> Self {
+        LayerTree {
+            nodes: Vec::new(),
         }
     }
 
-    pub fn parent_key_layer(&self, key: Id) -> Option<Id> {
-        let key = self.keys.get(&key)?;
-        let leaf_layer = key.layers.last()?;
-        Some(*leaf_layer)
+    pub fn with_nodes(nodes: Vec<SharedLayerNode>) -> Self {
+        LayerTree { nodes }
     }
 
-    pub fn layer(&self, id: Id) -> Option<&BoxedLayer> {
-        self.tree.node(id)
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
     }
 
-    pub fn layer_mut(&mut self, id: Id) -> Option<&mut BoxedLayer> {
-        self.tree.node_mut(id)
+    pub fn nodes(&self) -> &Vec<SharedLayerNode> {
+        &self.nodes
     }
 
-    pub fn draw(&self, canvas: &mut Canvas, size: impl Into<Size>, textures: &mut TextureRegistry) {
-        if let Some(children) = self.tree.children(None) {
-            for child in children {
-                self.draw_layer(canvas, textures, *child);
-            }
-        }
+    #[cfg(feature = "inspector")]
+    pub fn inspect(&self) -> Value {
+        Value::Array(self.nodes.iter().map(|node| node.inspect()).collect())
     }
 
-    fn draw_layer(&self, canvas: &mut Canvas, textures: &mut TextureRegistry, id: Id) {
-        if let Some(layer) = self.tree.node(id) {
-            let mut draw_children = move |canvas: &mut Canvas, textures: &mut TextureRegistry| {
-                if let Some(child_layers) = self.tree.children(id) {
-                    for child in child_layers {
-                        self.draw_layer(canvas, textures, *child);
-                    }
-                }
-            };
-            let size = {
-                let key = self
-                    .layer_key
-                    .get(&id)
-                    .expect("Layer does not have associated key!");
-                let key = self
-                    .keys
-                    .get(&key)
-                    .expect("Layer does not have associated key!");
-                key.size
-            };
-            layer.draw(&mut DrawContext::new(
-                size,
-                canvas,
-                textures,
-                &mut draw_children,
-            ));
+    pub fn draw(&self, canvas: &mut Canvas, textures: &mut TextureRegistry, raster_cache: &mut RasterCache) {
+        for node in &self.nodes {
+            node.draw(canvas, textures, raster_cache);
         }
     }
 }
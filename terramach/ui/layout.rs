@@ -35,6 +35,14 @@ impl Default for Fit {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum IntrinsicDimension {
+    MinWidth,
+    MaxWidth,
+    MinHeight,
+    MaxHeight,
+}
+
 pub trait MeasuredSize {
     fn new_unbound() -> Self;
 
@@ -165,6 +173,10 @@ impl Constraints {
         self.maximum_size
     }
 
+    pub fn is_tight(&self) -> bool {
+        self.minimum_size == self.maximum_size
+    }
+
     pub fn constrain(&self, constraints: &Constraints) -> Constraints {
         Constraints::new(
             self.minimum_size.constrain(constraints),
@@ -179,34 +191,105 @@ impl From<(Size, Size)> for Constraints {
     }
 }
 
+pub struct IntrinsicContext<'a> {
+    child_count: usize,
+    child_intrinsic: &'a dyn Fn(usize, IntrinsicDimension, f32) -> f32,
+}
+
+impl<'a> IntrinsicContext<'a> {
+    pub fn new<I>(child_count: usize, child_intrinsic: &'a I) -> Self
+        where I: Fn(usize, IntrinsicDimension, f32) -> f32 {
+        IntrinsicContext {
+            child_count,
+            child_intrinsic,
+        }
+    }
+
+    pub fn child_count(&self) -> usize {
+        self.child_count
+    }
+
+    pub fn min_intrinsic_width(&self, index: usize, height: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MinWidth, height)
+    }
+
+    pub fn max_intrinsic_width(&self, index: usize, height: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MaxWidth, height)
+    }
+
+    pub fn min_intrinsic_height(&self, index: usize, width: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MinHeight, width)
+    }
+
+    pub fn max_intrinsic_height(&self, index: usize, width: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MaxHeight, width)
+    }
+
+    pub fn max_of_children(&self, dimension: IntrinsicDimension, extent: f32) -> f32 {
+        (0..self.child_count)
+            .map(|index| (self.child_intrinsic)(index, dimension, extent))
+            .fold(0.0, f32::max)
+    }
+
+    pub fn sum_of_children(&self, dimension: IntrinsicDimension, extent: f32) -> f32 {
+        (0..self.child_count)
+            .map(|index| (self.child_intrinsic)(index, dimension, extent))
+            .sum()
+    }
+}
+
 pub struct LayoutContext<'a> {
     constraints: Constraints,
     child_count: usize,
     child_id: &'a dyn Fn(usize) -> Option<Id>,
-    layout_child: &'a mut dyn FnMut(Id, &Constraints) -> Option<Size>,
+    layout_child: &'a mut dyn FnMut(Id, &Constraints) -> Option<(Size, Option<f32>)>,
+    child_intrinsic: &'a dyn Fn(usize, IntrinsicDimension, f32) -> f32,
+    child_baselines: HashMap<usize, f32>,
     pub(crate) child_offsets: HashMap<Id, Point>,
+    pub(crate) baseline: Option<f32>,
 }
 
 impl<'a> LayoutContext<'a> {
-    pub fn new<C, L>(
+    pub fn new<C, L, I>(
         constraints: impl Into<Option<Constraints>>,
         child_count: usize,
         child_id: &'a C,
         layout_child: &'a mut L,
+        child_intrinsic: &'a I,
     ) -> Self where
         C: Fn(usize) -> Option<Id>,
-        L: FnMut(Id, &Constraints) -> Option<Size> {
+        L: FnMut(Id, &Constraints) -> Option<(Size, Option<f32>)>,
+        I: Fn(usize, IntrinsicDimension, f32) -> f32 {
         LayoutContext {
             constraints: constraints
                 .into()
                 .unwrap_or(Constraints::new_loose(Size::new_unbound())),
             child_count,
             child_offsets: HashMap::new(),
+            child_baselines: HashMap::new(),
             child_id,
             layout_child,
+            child_intrinsic,
+            baseline: None,
         }
     }
 
+    pub fn min_intrinsic_width(&self, index: usize, height: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MinWidth, height)
+    }
+
+    pub fn max_intrinsic_width(&self, index: usize, height: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MaxWidth, height)
+    }
+
+    pub fn min_intrinsic_height(&self, index: usize, width: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MinHeight, width)
+    }
+
+    pub fn max_intrinsic_height(&self, index: usize, width: f32) -> f32 {
+        (self.child_intrinsic)(index, IntrinsicDimension::MaxHeight, width)
+    }
+
     pub fn child_count(&self) -> usize {
         self.child_count
     }
@@ -217,7 +300,20 @@ impl<'a> LayoutContext<'a> {
 
     pub fn layout_child(&mut self, index: usize, constraints: &Constraints) -> Option<Size> {
         let child_id = (self.child_id)(index)?;
-        (self.layout_child)(child_id, &constraints)
+        let (size, baseline) = (self.layout_child)(child_id, &constraints)?;
+        match baseline {
+            Some(baseline) => self.child_baselines.insert(index, baseline),
+            None => self.child_baselines.remove(&index),
+        };
+        Some(size)
+    }
+
+    pub fn child_baseline(&self, index: usize) -> Option<f32> {
+        self.child_baselines.get(&index).copied()
+    }
+
+    pub fn set_baseline(&mut self, baseline: impl Into<Option<f32>>) {
+        self.baseline = baseline.into();
     }
 
     pub fn set_child_offset(&mut self, index: usize, offset: impl Into<Point>) {
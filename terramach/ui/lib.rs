@@ -26,16 +26,25 @@ extern crate objc;
 pub mod platform;
 pub mod gpu;
 pub mod widgets;
+#[cfg(feature = "description")]
+pub mod description;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod animation;
 mod app;
 mod bundle;
+mod clock;
 mod common;
+mod debug;
+mod error;
 mod event;
 mod gesture;
+mod handle;
 mod hit;
 mod layer;
 mod layers;
 mod layout;
+mod tasks;
 mod paint;
 mod timers;
 mod touch;
@@ -50,12 +59,17 @@ pub(crate) use app::*;
 
 pub use animation::*;
 pub use bundle::*;
+pub use clock::*;
+pub use debug::*;
+pub use error::*;
 pub use event::*;
 pub use gesture::*;
+pub use handle::*;
 pub use hit::*;
 pub use layer::*;
 pub use layers::*;
 pub use layout::*;
+pub use tasks::*;
 pub use paint::*;
 pub use timers::*;
 pub use touch::*;
@@ -65,9 +79,9 @@ pub use keys::*;
 
 pub use terramach_graphics as graphics;
 
-pub use terramach_support::{EventId, PartialWidget};
+pub use terramach_support::{view, EventId, PartialWidget, WidgetBuilder};
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 pub use terramach_support::noop_attribute as terramach_main;
 #[cfg(target_os = "android")]
 pub use terramach_support::terramach_main;
@@ -54,6 +54,16 @@ impl PaintContext {
         self.leaf_layer.as_ref()
     }
 
+    pub fn take_layers(&mut self) -> Option<ContainerLayer> {
+        self.push_current_if_any();
+        self.layer.take()
+    }
+
+    pub fn take_leaf_layers(&mut self) -> Option<ContainerLayer> {
+        self.push_current_if_any();
+        self.leaf_layer.take()
+    }
+
     pub fn canvas(&mut self) -> &mut Canvas {
         let bounds = Rect::from_size(self.size);
         let recorder = self.recorder.get_or_insert_with(|| {
@@ -18,22 +18,35 @@
 
 use std::sync::Mutex;
 
-use crate::{AppEvents, BoxedWidget};
+use crate::{AppEvents, BoxedWidget, DebugPaint, DebugPaintSettings, ErrorHandler, InspectorHandler, WidgetError};
+use crate::gpu::{FrameTimings, FrameTimingsHandler};
 use crate::platform::RunLoop;
 
 use terramach_graphics::{gl, Display, Color, Paint, ISize, Rect};
 use terramach_graphics::gl::egl;
 
+use flagset::FlagSet;
 use lazy_static::lazy_static;
 
+#[cfg(feature = "inspector")]
+use serde_json::Value;
+
 pub struct App {
     content: Option<BoxedWidget>,
+    error_handler: Option<ErrorHandler>,
+    debug_paint: DebugPaintSettings,
+    frame_timings: Option<FrameTimingsHandler>,
+    inspector: Option<InspectorHandler>,
 }
 
 impl App {
     pub fn new() -> Self {
         App {
             content: None,
+            error_handler: None,
+            debug_paint: DebugPaintSettings::default(),
+            frame_timings: None,
+            inspector: None,
         }
     }
 
@@ -47,6 +60,31 @@ impl App {
         self
     }
 
+    pub fn with_error_handler(mut self, error_handler: impl FnMut(&WidgetError) + 'static) -> Self {
+        self.error_handler = Some(Box::new(error_handler));
+        self
+    }
+
+    pub fn with_debug_paint(self, flags: impl Into<FlagSet<DebugPaint>>) -> Self {
+        self.debug_paint.set(flags);
+        self
+    }
+
+    pub fn debug_paint(&self) -> DebugPaintSettings {
+        self.debug_paint.clone()
+    }
+
+    pub fn with_frame_timings(mut self, frame_timings: impl FnMut(&FrameTimings) + Send + 'static) -> Self {
+        self.frame_timings = Some(Box::new(frame_timings));
+        self
+    }
+
+    #[cfg(feature = "inspector")]
+    pub fn with_inspector(mut self, inspector: impl FnMut(&Value) + 'static) -> Self {
+        self.inspector = Some(Box::new(inspector));
+        self
+    }
+
     pub fn run(mut self, content: impl Into<BoxedWidget>) {
         self.content = content.into().into();
         set_current_app(self);
@@ -55,6 +93,18 @@ impl App {
     pub(crate) fn take_content(&mut self) -> Option<BoxedWidget> {
         self.content.take()
     }
+
+    pub(crate) fn take_error_handler(&mut self) -> Option<ErrorHandler> {
+        self.error_handler.take()
+    }
+
+    pub(crate) fn take_frame_timings(&mut self) -> Option<FrameTimingsHandler> {
+        self.frame_timings.take()
+    }
+
+    pub(crate) fn take_inspector(&mut self) -> Option<InspectorHandler> {
+        self.inspector.take()
+    }
 }
 
 pub(crate) fn new_app() -> Option<App> {
@@ -135,6 +135,10 @@ pub fn Java_com_terramach_TerraMachView_runApp(env: JNIEnv, obj: JObject, surfac
 
     let mut app = platform::new_app().expect("No app is running");
     let content = app.take_content().expect("App is empty");
+    let error_handler = app.take_error_handler();
+    let debug_paint = app.debug_paint();
+    let frame_timings = app.take_frame_timings();
+    let inspector = app.take_inspector();
     let run_loop = RunLoop::new();
     let mut app_events = AppEvents::new();
     env.set_rust_field(
@@ -151,6 +155,10 @@ pub fn Java_com_terramach_TerraMachView_runApp(env: JNIEnv, obj: JObject, surfac
         app_events,
         display,
         content,
+        error_handler,
+        debug_paint,
+        frame_timings,
+        inspector,
     );
 }
 
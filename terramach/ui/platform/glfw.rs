@@ -21,15 +21,104 @@ use std::sync::mpsc::Receiver;
 use glfw;
 use glfw::Context;
 
+use flagset::FlagSet;
+
+#[cfg(feature = "inspector")]
+use serde_json::Value;
+
 use terramach_graphics::{ISize, Size, Point, Display};
 use terramach_graphics::gl;
 
+use crate::gpu::{FrameTimings, FrameTimingsHandler};
 use crate::platform::RunLoop;
-use crate::{AppEvents, TouchTracker, KeyTracker, AppEvent, KeyModifier, KeyScanCode, KeyAction, TouchId, Widget, BoxedWidget, run_app};
+use crate::{AppEvents, TouchTracker, KeyTracker, AppEvent, KeyModifier, KeyScanCode, KeyAction, TouchId, Widget, BoxedWidget, ErrorHandler, InspectorHandler, WidgetError, DebugPaint, DebugPaintSettings, run_app};
+
+#[cfg(target_os = "macos")]
+fn key_scan_code(_key: glfw::Key, scan_code: glfw::Scancode) -> Option<KeyScanCode> {
+    Some(scan_code as KeyScanCode)
+}
+
+#[cfg(target_os = "linux")]
+fn key_scan_code(key: glfw::Key, _scan_code: glfw::Scancode) -> Option<KeyScanCode> {
+    use crate::Key;
+    let key = match key {
+        glfw::Key::Num1 => Key::Num1,
+        glfw::Key::Num2 => Key::Num2,
+        glfw::Key::Num3 => Key::Num3,
+        glfw::Key::Num4 => Key::Num4,
+        glfw::Key::Num5 => Key::Num5,
+        glfw::Key::Num6 => Key::Num6,
+        glfw::Key::Num7 => Key::Num7,
+        glfw::Key::Num8 => Key::Num8,
+        glfw::Key::Num9 => Key::Num9,
+        glfw::Key::Num0 => Key::Num0,
+        glfw::Key::Backspace => Key::BackSpace,
+        glfw::Key::Escape => Key::Escape,
+        glfw::Key::Space => Key::Space,
+        glfw::Key::LeftSuper => Key::LeftCommand,
+        glfw::Key::RightSuper => Key::RightCommand,
+        glfw::Key::LeftAlt => Key::LeftAlt,
+        glfw::Key::RightAlt => Key::RightAlt,
+        glfw::Key::LeftControl | glfw::Key::RightControl => Key::Control,
+        glfw::Key::Tab => Key::Tab,
+        glfw::Key::CapsLock => Key::CapsLock,
+        glfw::Key::LeftShift => Key::LeftShift,
+        glfw::Key::RightShift => Key::RightShift,
+        glfw::Key::Q => Key::Q,
+        glfw::Key::W => Key::W,
+        glfw::Key::E => Key::E,
+        glfw::Key::R => Key::R,
+        glfw::Key::T => Key::T,
+        glfw::Key::Y => Key::Y,
+        glfw::Key::U => Key::U,
+        glfw::Key::I => Key::I,
+        glfw::Key::O => Key::O,
+        glfw::Key::P => Key::P,
+        glfw::Key::A => Key::A,
+        glfw::Key::S => Key::S,
+        glfw::Key::D => Key::D,
+        glfw::Key::F => Key::F,
+        glfw::Key::G => Key::G,
+        glfw::Key::H => Key::H,
+        glfw::Key::J => Key::J,
+        glfw::Key::K => Key::K,
+        glfw::Key::L => Key::L,
+        glfw::Key::Z => Key::Z,
+        glfw::Key::X => Key::X,
+        glfw::Key::C => Key::C,
+        glfw::Key::V => Key::V,
+        glfw::Key::B => Key::B,
+        glfw::Key::N => Key::N,
+        glfw::Key::M => Key::M,
+        glfw::Key::GraveAccent => Key::GraveAccent,
+        glfw::Key::Minus => Key::Minus,
+        glfw::Key::Equal => Key::Equal,
+        glfw::Key::LeftBracket => Key::LeftBracket,
+        glfw::Key::RightBracket => Key::RightBracket,
+        glfw::Key::Backslash => Key::Backslash,
+        glfw::Key::Semicolon => Key::Semicolon,
+        glfw::Key::Apostrophe => Key::Apostrophe,
+        glfw::Key::Comma => Key::Comma,
+        glfw::Key::Period => Key::Period,
+        glfw::Key::Slash => Key::Slash,
+        glfw::Key::Enter | glfw::Key::KpEnter => Key::Enter,
+        glfw::Key::Left => Key::Left,
+        glfw::Key::Right => Key::Right,
+        glfw::Key::Up => Key::Up,
+        glfw::Key::Down => Key::Down,
+        glfw::Key::Delete => Key::Delete,
+        _ => return None,
+    };
+    Some(key as KeyScanCode)
+}
 
 pub struct App {
     title: Option<String>,
     size: Option<ISize>,
+    error_handler: Option<ErrorHandler>,
+    debug_paint: DebugPaintSettings,
+    frame_timings: Option<FrameTimingsHandler>,
+    inspector: Option<InspectorHandler>,
 }
 
 impl App {
@@ -37,6 +126,10 @@ impl App {
         App {
             title: None,
             size: None,
+            error_handler: None,
+            debug_paint: DebugPaintSettings::default(),
+            frame_timings: None,
+            inspector: None,
         }
     }
 
@@ -50,6 +143,31 @@ impl App {
         self
     }
 
+    pub fn with_error_handler(mut self, error_handler: impl FnMut(&WidgetError) + 'static) -> Self {
+        self.error_handler = Some(Box::new(error_handler));
+        self
+    }
+
+    pub fn with_debug_paint(self, flags: impl Into<FlagSet<DebugPaint>>) -> Self {
+        self.debug_paint.set(flags);
+        self
+    }
+
+    pub fn debug_paint(&self) -> DebugPaintSettings {
+        self.debug_paint.clone()
+    }
+
+    pub fn with_frame_timings(mut self, frame_timings: impl FnMut(&FrameTimings) + Send + 'static) -> Self {
+        self.frame_timings = Some(Box::new(frame_timings));
+        self
+    }
+
+    #[cfg(feature = "inspector")]
+    pub fn with_inspector(mut self, inspector: impl FnMut(&Value) + 'static) -> Self {
+        self.inspector = Some(Box::new(inspector));
+        self
+    }
+
     pub fn run(self, content: impl Into<BoxedWidget>) {
         let mut glfw = glfw::init(glfw::FAIL_ON_ERRORS)
             .expect("Failed to initialize GLFW");
@@ -74,6 +192,9 @@ impl App {
         window.set_char_polling(true);
         window.set_focus_polling(true);
 
+        #[cfg(target_os = "linux")]
+        crate::platform::set_current_window(window.window_ptr());
+
         let mut display = gl::Display::new(
             size,
             window,
@@ -84,6 +205,17 @@ impl App {
         ).expect("Failed to create a display in GL window");
 
         let mut run_loop = RunLoop::new();
+        #[cfg(target_os = "linux")]
+        run_loop.set_event_source(
+            |timeout| unsafe {
+                match timeout {
+                    Some(timeout) if timeout.as_nanos() == 0 => glfw::ffi::glfwPollEvents(),
+                    Some(timeout) => glfw::ffi::glfwWaitEventsTimeout(timeout.as_secs_f64()),
+                    None => glfw::ffi::glfwWaitEvents(),
+                }
+            },
+            || unsafe { glfw::ffi::glfwPostEmptyEvent() },
+        );
         let mut app_events = AppEvents::new();
         let mut event_emitter = app_events.emitter();
         let mut touches = TouchTracker::new();
@@ -94,6 +226,10 @@ impl App {
         let mut pinch_locked = None;
 
         run_loop.add_observer(move || {
+            // Cocoa blocks inside its own loop during a live resize, so resizes are taken
+            // unbuffered there. On Linux the event source pumps GLFW and resizes arrive
+            // buffered with the rest of the window events.
+            #[cfg(target_os = "macos")]
             glfw.poll_events_unbuffered(|_, event| match event.1 {
                 glfw::WindowEvent::Size(width, height) => {
                     event_emitter.emit_event(AppEvent::Resize(Size::new(width as f32, height as f32)));
@@ -106,6 +242,10 @@ impl App {
                     glfw::WindowEvent::Close => {
                         event_emitter.emit_event(AppEvent::Quit);
                     }
+                    #[cfg(target_os = "linux")]
+                    glfw::WindowEvent::Size(width, height) => {
+                        event_emitter.emit_event(AppEvent::Resize(Size::new(width as f32, height as f32)));
+                    }
                     glfw::WindowEvent::Focus(focused) => {
                         touches.reset();
                         event_emitter.emit_event(AppEvent::Focus(focused));
@@ -159,11 +299,13 @@ impl App {
                                 _ => {}
                             }
                         }
-                        keys.push_scan_code(scan_code as KeyScanCode);
-                        match action {
-                            glfw::Action::Release => keys.push_action(KeyAction::Release),
-                            glfw::Action::Press => keys.push_action(KeyAction::Press),
-                            glfw::Action::Repeat => keys.push_action(KeyAction::Repeat),
+                        if let Some(scan_code) = key_scan_code(key, scan_code) {
+                            keys.push_scan_code(scan_code);
+                            match action {
+                                glfw::Action::Release => keys.push_action(KeyAction::Release),
+                                glfw::Action::Press => keys.push_action(KeyAction::Press),
+                                glfw::Action::Repeat => keys.push_action(KeyAction::Repeat),
+                            }
                         }
                         keys.clear_modifiers(KeyModifier::Shift | KeyModifier::NumLock | KeyModifier::Alt | KeyModifier::Control);
                         if modifiers & glfw::Modifiers::Shift == glfw::Modifiers::Shift {
@@ -204,6 +346,10 @@ impl App {
             app_events,
             display,
             content,
+            self.error_handler,
+            self.debug_paint,
+            self.frame_timings,
+            self.inspector,
         );
     }
 }
@@ -0,0 +1,162 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::sync::{Arc, Mutex};
+
+use terramach_graphics::{raster, Image, ISize, Size};
+use terramach_graphics::raster::DisplaySnapshot;
+
+use crate::gpu::{FrameTimings, FrameTimingsHandler};
+use crate::platform::{RunLoop, SharedRunLoop};
+use crate::{AppEvent, AppEvents, BoxedWidget, DebugPaint, DebugPaintSettings, ErrorHandler, InspectorHandler, EventEmitter, WidgetError, run_app};
+
+use flagset::FlagSet;
+
+#[cfg(feature = "inspector")]
+use serde_json::Value;
+
+#[derive(Clone)]
+pub struct AppHandle {
+    event_emitter: EventEmitter<AppEvent>,
+    run_loop: SharedRunLoop,
+    snapshot: DisplaySnapshot,
+    title: Arc<Mutex<Option<String>>>,
+}
+
+impl AppHandle {
+    pub fn emit_event(&mut self, event: AppEvent) {
+        self.event_emitter.emit_event(event);
+        self.run_loop.wakeup();
+    }
+
+    pub fn resize(&mut self, size: impl Into<Size>) {
+        self.emit_event(AppEvent::Resize(size.into()));
+    }
+
+    pub fn quit(&mut self) {
+        self.emit_event(AppEvent::Quit);
+    }
+
+    pub fn last_frame(&self) -> Option<Image> {
+        self.snapshot.image()
+    }
+
+    /// The title the app was given. A headless app has no window to show it in.
+    pub fn title(&self) -> Option<String> {
+        self.title.lock().ok()?.clone()
+    }
+}
+
+pub struct App {
+    size: Option<ISize>,
+    title: Arc<Mutex<Option<String>>>,
+    pixel_ratio: Option<f32>,
+    run_loop: RunLoop,
+    events: AppEvents,
+    snapshot: DisplaySnapshot,
+    error_handler: Option<ErrorHandler>,
+    debug_paint: DebugPaintSettings,
+    frame_timings: Option<FrameTimingsHandler>,
+    inspector: Option<InspectorHandler>,
+}
+
+impl App {
+    pub fn new() -> Self {
+        App {
+            size: None,
+            title: Default::default(),
+            pixel_ratio: None,
+            run_loop: RunLoop::new(),
+            events: AppEvents::new(),
+            snapshot: DisplaySnapshot::new(),
+            error_handler: None,
+            debug_paint: DebugPaintSettings::default(),
+            frame_timings: None,
+            inspector: None,
+        }
+    }
+
+    pub fn with_size(mut self, size: impl Into<ISize>) -> Self {
+        self.size = size.into().into();
+        self
+    }
+
+    pub fn with_title(self, title: impl AsRef<str>) -> Self {
+        if let Ok(mut current) = self.title.lock() {
+            *current = Some(title.as_ref().to_string());
+        }
+        self
+    }
+
+    pub fn with_pixel_ratio(mut self, pixel_ratio: f32) -> Self {
+        self.pixel_ratio = pixel_ratio.into();
+        self
+    }
+
+    pub fn with_error_handler(mut self, error_handler: impl FnMut(&WidgetError) + 'static) -> Self {
+        self.error_handler = Some(Box::new(error_handler));
+        self
+    }
+
+    pub fn with_debug_paint(self, flags: impl Into<FlagSet<DebugPaint>>) -> Self {
+        self.debug_paint.set(flags);
+        self
+    }
+
+    pub fn debug_paint(&self) -> DebugPaintSettings {
+        self.debug_paint.clone()
+    }
+
+    pub fn with_frame_timings(mut self, frame_timings: impl FnMut(&FrameTimings) + Send + 'static) -> Self {
+        self.frame_timings = Some(Box::new(frame_timings));
+        self
+    }
+
+    #[cfg(feature = "inspector")]
+    pub fn with_inspector(mut self, inspector: impl FnMut(&Value) + 'static) -> Self {
+        self.inspector = Some(Box::new(inspector));
+        self
+    }
+
+    pub fn handle(&mut self) -> AppHandle {
+        AppHandle {
+            event_emitter: self.events.emitter(),
+            run_loop: self.run_loop.share(),
+            snapshot: self.snapshot.clone(),
+            title: self.title.clone(),
+        }
+    }
+
+    pub fn run(self, content: impl Into<BoxedWidget>) {
+        let size = self.size.unwrap_or(ISize::new(640, 380));
+        let display = raster::Display::new(
+            Size::new(size.width as f32, size.height as f32),
+            self.pixel_ratio,
+        ).with_snapshot(self.snapshot);
+        run_app(
+            self.run_loop,
+            self.events,
+            display,
+            content,
+            self.error_handler,
+            self.debug_paint,
+            self.frame_timings,
+            self.inspector,
+        );
+    }
+}
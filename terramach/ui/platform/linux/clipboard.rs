@@ -0,0 +1,97 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::ffi::{CStr, CString};
+
+use glfw::ffi;
+
+use crate::platform::current_window;
+
+#[derive(Debug)]
+pub struct ClipboardContent {
+    data: String,
+}
+
+impl ClipboardContent {
+    pub fn to_string(&self) -> Option<String> {
+        self.data.clone().into()
+    }
+}
+
+impl From<String> for ClipboardContent {
+    fn from(data: String) -> Self {
+        ClipboardContent {
+            data,
+        }
+    }
+}
+
+impl From<&str> for ClipboardContent {
+    fn from(data: &str) -> Self {
+        ClipboardContent::from(data.to_string())
+    }
+}
+
+impl From<&String> for ClipboardContent {
+    fn from(data: &String) -> Self {
+        ClipboardContent::from(data.clone())
+    }
+}
+
+pub struct Clipboard {}
+
+impl Clipboard {
+    fn new() -> Self {
+        Clipboard {}
+    }
+
+    pub fn clear_content(&mut self) {
+        self.set_content("");
+    }
+
+    pub fn set_content(&mut self, content: impl Into<ClipboardContent>) -> bool {
+        let content = content.into();
+        let window = match current_window() {
+            Some(window) => window,
+            None => return false,
+        };
+        let data = match CString::new(content.data) {
+            Ok(data) => data,
+            Err(_) => return false,
+        };
+        unsafe {
+            ffi::glfwSetClipboardString(window, data.as_ptr());
+        }
+        true
+    }
+
+    pub fn content(&self) -> Option<ClipboardContent> {
+        let window = current_window()?;
+        unsafe {
+            let data = ffi::glfwGetClipboardString(window);
+            if data.is_null() { return None; }
+            Some(ClipboardContent::from(CStr::from_ptr(data).to_string_lossy().to_string()))
+        }
+    }
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Clipboard::new()
+    }
+}
@@ -0,0 +1,89 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::os::raw::c_int;
+
+use glfw::ffi;
+
+use crate::platform::current_window;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Cursor {
+    Arrow,
+    Text,
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Cursor::Arrow
+    }
+}
+
+impl Cursor {
+    fn shape(&self) -> c_int {
+        match self {
+            Cursor::Arrow => ffi::ARROW_CURSOR,
+            Cursor::Text => ffi::IBEAM_CURSOR,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Cursors {
+    stack: Vec<Cursor>,
+    arrow: Option<*mut ffi::GLFWcursor>,
+    text: Option<*mut ffi::GLFWcursor>,
+}
+
+impl Cursors {
+    pub fn new() -> Self {
+        Cursors::default()
+    }
+
+    fn native_cursor(&mut self, cursor: Cursor) -> *mut ffi::GLFWcursor {
+        let native = match cursor {
+            Cursor::Arrow => &mut self.arrow,
+            Cursor::Text => &mut self.text,
+        };
+        *native.get_or_insert_with(|| unsafe {
+            ffi::glfwCreateStandardCursor(cursor.shape())
+        })
+    }
+
+    fn apply(&mut self) {
+        let window = match current_window() {
+            Some(window) => window,
+            None => return,
+        };
+        let cursor = self.stack.last().cloned().unwrap_or_default();
+        let cursor = self.native_cursor(cursor);
+        unsafe {
+            ffi::glfwSetCursor(window, cursor);
+        }
+    }
+
+    pub fn push(&mut self, cursor: impl Into<Option<Cursor>>) {
+        self.stack.push(cursor.into().unwrap_or_default());
+        self.apply();
+    }
+
+    pub fn pop(&mut self) {
+        self.stack.pop();
+        self.apply();
+    }
+}
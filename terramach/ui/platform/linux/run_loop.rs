@@ -0,0 +1,238 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::collections::HashMap;
+use std::ops::{Add, AddAssign};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+
+#[derive(Default, Hash, Copy, Clone, Eq, PartialEq)]
+pub struct RunLoopHandle(usize);
+
+impl Add<usize> for RunLoopHandle {
+    type Output = RunLoopHandle;
+
+    fn add(self, rhs: usize) -> Self::Output {
+        RunLoopHandle(self.0.wrapping_add(rhs))
+    }
+}
+
+impl AddAssign<usize> for RunLoopHandle {
+    fn add_assign(&mut self, rhs: usize) {
+        self.0 = self.0.wrapping_add(rhs);
+    }
+}
+
+type WaitFunction = Box<dyn FnMut(Option<Duration>)>;
+type WakeupFunction = Box<dyn Send + Sync + Fn()>;
+
+struct RunLoopState {
+    running: AtomicBool,
+    signaled: Mutex<bool>,
+    condvar: Condvar,
+    next_fire_time: Mutex<Option<Instant>>,
+    wakeup: Mutex<Option<WakeupFunction>>,
+}
+
+impl RunLoopState {
+    fn new() -> Self {
+        RunLoopState {
+            running: AtomicBool::new(false),
+            signaled: Mutex::new(false),
+            condvar: Condvar::new(),
+            next_fire_time: Mutex::new(None),
+            wakeup: Mutex::new(None),
+        }
+    }
+
+    fn signal(&self) {
+        if let Ok(mut signaled) = self.signaled.lock() {
+            *signaled = true;
+            self.condvar.notify_one();
+        }
+        if let Ok(wakeup) = self.wakeup.lock() {
+            if let Some(wakeup) = wakeup.as_ref() {
+                (wakeup)();
+            }
+        }
+    }
+
+    fn take_signal(&self) -> bool {
+        if let Ok(mut signaled) = self.signaled.lock() {
+            let result = *signaled;
+            *signaled = false;
+            result
+        } else {
+            false
+        }
+    }
+
+    fn set_next_fire_time(&self, time: Duration) {
+        if let Ok(mut next_fire_time) = self.next_fire_time.lock() {
+            *next_fire_time = Some(Instant::now() + time);
+        }
+    }
+
+    fn next_timeout(&self) -> Option<Duration> {
+        let mut next_fire_time = self.next_fire_time.lock().ok()?;
+        let now = Instant::now();
+        match *next_fire_time {
+            Some(time) if time <= now => {
+                *next_fire_time = None;
+                Some(Duration::from_secs(0))
+            }
+            Some(time) => Some(time - now),
+            None => None,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SharedRunLoop {
+    state: Arc<RunLoopState>,
+}
+
+impl SharedRunLoop {
+    fn new(run_loop: &RunLoop) -> Self {
+        SharedRunLoop {
+            state: run_loop.state.clone(),
+        }
+    }
+
+    pub fn wakeup(&mut self) {
+        self.state.signal();
+    }
+
+    pub fn stop(&mut self) {
+        self.state.running.store(false, Ordering::Relaxed);
+        self.wakeup();
+    }
+
+    pub fn set_next_wakeup_in(&mut self, delay: Duration) {
+        self.state.set_next_fire_time(delay);
+    }
+}
+
+pub struct RunLoop {
+    state: Arc<RunLoopState>,
+    last_handle: RunLoopHandle,
+    observers: HashMap<RunLoopHandle, Box<dyn FnMut()>>,
+    wait: Option<WaitFunction>,
+}
+
+impl RunLoop {
+    pub fn main() -> Self {
+        RunLoop::new()
+    }
+
+    pub fn new() -> Self {
+        RunLoop {
+            state: Arc::new(RunLoopState::new()),
+            last_handle: RunLoopHandle::default(),
+            observers: HashMap::new(),
+            wait: None,
+        }
+    }
+
+    pub fn share(&self) -> SharedRunLoop {
+        SharedRunLoop::new(self)
+    }
+
+    /// Replaces the default blocking wait with an external event source, such as a windowing
+    /// system queue. `wait` blocks for at most the given timeout (indefinitely on `None`), and
+    /// `wakeup` must unblock it from any thread.
+    pub fn set_event_source<W: 'static, F: 'static>(&mut self, wait: W, wakeup: F)
+        where W: FnMut(Option<Duration>), F: Send + Sync + Fn() {
+        self.wait = Some(Box::new(wait));
+        if let Ok(mut current_wakeup) = self.state.wakeup.lock() {
+            *current_wakeup = Some(Box::new(wakeup));
+        }
+    }
+
+    pub fn add_observer<F: 'static>(&mut self, observer: F) -> RunLoopHandle where F: FnMut() {
+        self.last_handle += 1;
+        let observer = Box::new(observer);
+        self.observers.insert(self.last_handle, observer);
+        self.last_handle
+    }
+
+    pub fn remove_observer(&mut self, observer: impl AsRef<RunLoopHandle>) {
+        let _ = self.observers.remove(observer.as_ref());
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.state.running.load(Ordering::Relaxed)
+    }
+
+    fn set_running(&mut self, running: bool) {
+        self.state.running.store(running, Ordering::Relaxed);
+    }
+
+    fn wait(&mut self) {
+        let timeout = self.state.next_timeout();
+        if let Some(wait) = &mut self.wait {
+            if !self.state.take_signal() {
+                (wait)(timeout);
+                self.state.take_signal();
+            }
+            return;
+        }
+        if let Ok(mut signaled) = self.state.signaled.lock() {
+            while !*signaled {
+                match timeout {
+                    Some(timeout) => {
+                        match self.state.condvar.wait_timeout(signaled, timeout) {
+                            Ok((guard, result)) => {
+                                signaled = guard;
+                                if result.timed_out() {
+                                    break;
+                                }
+                            }
+                            Err(_) => return,
+                        }
+                    }
+                    None => {
+                        match self.state.condvar.wait(signaled) {
+                            Ok(guard) => signaled = guard,
+                            Err(_) => return,
+                        }
+                    }
+                }
+            }
+            *signaled = false;
+        }
+    }
+
+    pub fn run(&mut self) {
+        self.set_running(true);
+        while self.is_running() {
+            for observer in self.observers.values_mut() {
+                (observer)();
+            }
+            if self.is_running() {
+                self.wait();
+            }
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.set_running(false);
+        self.state.signal();
+    }
+}
@@ -0,0 +1,96 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::sync::mpsc::channel;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use lazy_static::lazy_static;
+use time_point::TimePoint;
+
+const FRAME_INTERVAL: Duration = Duration::from_nanos(16_666_667);
+
+lazy_static! {
+    static ref SHARED: VSync = VSync::new().expect("Failed to initialize VSync!");
+}
+
+type FrameCallback = Box<dyn Send + FnMut(TimePoint)>;
+
+#[derive(Clone)]
+pub struct VSync {
+    callbacks: Arc<Mutex<Vec<FrameCallback>>>,
+}
+
+impl VSync {
+    pub fn new() -> Option<Self> {
+        let callbacks: Arc<Mutex<Vec<FrameCallback>>> = Arc::new(Mutex::new(Vec::new()));
+        let timer_callbacks = callbacks.clone();
+        thread::Builder::new()
+            .name("VSync".to_string())
+            .spawn(move || {
+                let start = Instant::now();
+                let mut next_frame = start + FRAME_INTERVAL;
+                loop {
+                    let now = Instant::now();
+                    if next_frame > now {
+                        thread::sleep(next_frame - now);
+                    }
+                    let callbacks: Vec<FrameCallback> =
+                        if let Ok(mut callbacks) = timer_callbacks.lock() {
+                            callbacks.drain(..).collect()
+                        } else {
+                            return;
+                        };
+                    let timestamp = TimePoint::new(next_frame.duration_since(start).as_nanos() as i64);
+                    for mut callback in callbacks {
+                        callback(timestamp);
+                    }
+                    next_frame += FRAME_INTERVAL;
+                    let now = Instant::now();
+                    if next_frame < now {
+                        next_frame = now + FRAME_INTERVAL;
+                    }
+                }
+            })
+            .ok()?;
+        Some(VSync {
+            callbacks,
+        })
+    }
+
+    pub fn wait(&mut self) -> Option<TimePoint> {
+        let (tx, rx) = channel();
+        self.request_frame(move |timestamp| {
+            let _ = tx.send(timestamp);
+        });
+        rx.recv().ok()
+    }
+
+    pub fn request_frame<F>(&mut self, callback: F) where F: 'static + Send + FnMut(TimePoint) {
+        if let Ok(mut callbacks) = self.callbacks.lock() {
+            callbacks.push(Box::new(callback));
+        }
+    }
+}
+
+impl Default for VSync {
+    fn default() -> Self {
+        SHARED.clone()
+    }
+}
@@ -0,0 +1,37 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+use glfw::ffi::GLFWwindow;
+
+static CURRENT_WINDOW: AtomicPtr<GLFWwindow> = AtomicPtr::new(null_mut());
+
+pub(crate) fn set_current_window(window: *mut GLFWwindow) {
+    CURRENT_WINDOW.store(window, Ordering::Relaxed);
+}
+
+pub(crate) fn current_window() -> Option<*mut GLFWwindow> {
+    let window = CURRENT_WINDOW.load(Ordering::Relaxed);
+    if window.is_null() {
+        None
+    } else {
+        Some(window)
+    }
+}
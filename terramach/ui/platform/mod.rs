@@ -16,6 +16,8 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
+pub mod headless;
+
 #[cfg(target_os = "android")]
 mod android;
 
@@ -28,8 +30,14 @@ mod darwin;
 #[cfg(any(target_os = "macos", target_os = "ios"))]
 pub use darwin::*;
 
-#[cfg(target_os = "macos")]
+#[cfg(target_os = "linux")]
+mod linux;
+
+#[cfg(target_os = "linux")]
+pub use linux::*;
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 mod glfw;
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 pub use crate::platform::glfw::*;
@@ -0,0 +1,285 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::mem::ManuallyDrop;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread;
+
+use crate::{Id, WidgetEventSender, panic_message};
+
+use lazy_static::lazy_static;
+
+const BLOCKING_THREADS: usize = 4;
+
+pub type TaskId = Id;
+
+type LocalTask = Pin<Box<dyn Future<Output = ()>>>;
+
+#[derive(Debug)]
+pub struct TaskOutput<T> {
+    task: TaskId,
+    output: Mutex<Option<T>>,
+}
+
+impl<T> TaskOutput<T> {
+    fn new(task: TaskId, output: T) -> Self {
+        TaskOutput {
+            task,
+            output: Mutex::new(Some(output)),
+        }
+    }
+
+    pub fn task(&self) -> TaskId {
+        self.task
+    }
+
+    pub fn take(&self) -> Option<T> {
+        self.output.lock().ok()?.take()
+    }
+}
+
+pub(crate) struct Tasks {
+    last_id: TaskId,
+    tasks: HashMap<TaskId, LocalTask>,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        Tasks {
+            last_id: 0,
+            tasks: HashMap::new(),
+        }
+    }
+
+    pub fn spawn<T: Send + 'static>(
+        &mut self,
+        future: impl Future<Output = T> + 'static,
+        event_sender: WidgetEventSender,
+    ) -> TaskId {
+        self.last_id += 1;
+        let id = self.last_id;
+        let output_sender = event_sender.clone();
+        self.tasks.insert(
+            id,
+            Box::pin(async move {
+                let output = future.await;
+                output_sender.send_custom(TaskOutput::new(id, output));
+            }),
+        );
+        TaskWaker::new(id, event_sender).wake();
+        id
+    }
+
+    pub fn poll(&mut self, id: TaskId, event_sender: WidgetEventSender) {
+        if let Some(mut task) = self.tasks.remove(&id) {
+            let waker = TaskWaker::new(id, event_sender).into_waker();
+            let mut context = Context::from_waker(&waker);
+            if task.as_mut().poll(&mut context).is_pending() {
+                self.tasks.insert(id, task);
+            }
+        }
+    }
+
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        self.tasks.remove(&id).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    pub fn ids(&self) -> Vec<TaskId> {
+        self.tasks.keys().copied().collect()
+    }
+}
+
+struct TaskWaker {
+    task: TaskId,
+    event_sender: WidgetEventSender,
+}
+
+impl TaskWaker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(
+        TaskWaker::clone_raw,
+        TaskWaker::wake_raw,
+        TaskWaker::wake_by_ref_raw,
+        TaskWaker::drop_raw,
+    );
+
+    fn new(task: TaskId, event_sender: WidgetEventSender) -> Arc<Self> {
+        Arc::new(TaskWaker { task, event_sender })
+    }
+
+    fn wake(&self) {
+        let task = self.task;
+        let event_sender = self.event_sender.clone();
+        self.event_sender.send_callback(move |context, _| {
+            context.tasks.poll(task, event_sender);
+        });
+    }
+
+    fn into_waker(self: Arc<Self>) -> Waker {
+        unsafe { Waker::from_raw(TaskWaker::raw(self)) }
+    }
+
+    fn raw(waker: Arc<Self>) -> RawWaker {
+        RawWaker::new(Arc::into_raw(waker) as *const (), &TaskWaker::VTABLE)
+    }
+
+    unsafe fn clone_raw(data: *const ()) -> RawWaker {
+        let waker = ManuallyDrop::new(Arc::from_raw(data as *const TaskWaker));
+        TaskWaker::raw(Arc::clone(&waker))
+    }
+
+    unsafe fn wake_raw(data: *const ()) {
+        let waker = Arc::from_raw(data as *const TaskWaker);
+        waker.wake();
+    }
+
+    unsafe fn wake_by_ref_raw(data: *const ()) {
+        let waker = ManuallyDrop::new(Arc::from_raw(data as *const TaskWaker));
+        waker.wake();
+    }
+
+    unsafe fn drop_raw(data: *const ()) {
+        drop(Arc::from_raw(data as *const TaskWaker));
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+struct ThreadPool {
+    jobs: Mutex<Sender<Job>>,
+}
+
+impl ThreadPool {
+    fn new(threads: usize) -> Self {
+        let (jobs, receiver) = mpsc::channel();
+        let receiver = Arc::new(Mutex::new(receiver));
+        for index in 0..threads {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("terramach-blocking-{}", index))
+                .spawn(move || ThreadPool::run(receiver))
+                .expect("Failed to spawn blocking thread");
+        }
+        ThreadPool {
+            jobs: Mutex::new(jobs),
+        }
+    }
+
+    fn run(receiver: Arc<Mutex<Receiver<Job>>>) {
+        loop {
+            let job = match receiver.lock() {
+                Ok(receiver) => receiver.recv(),
+                Err(_) => return,
+            };
+            match job {
+                Ok(job) => {
+                    let _ = panic::catch_unwind(AssertUnwindSafe(job));
+                }
+                Err(_) => return,
+            }
+        }
+    }
+
+    fn execute(&self, job: Job) {
+        if let Ok(jobs) = self.jobs.lock() {
+            let _ = jobs.send(job);
+        }
+    }
+}
+
+lazy_static! {
+    static ref BLOCKING_POOL: ThreadPool = ThreadPool::new(BLOCKING_THREADS);
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BlockingError {
+    Panicked(String),
+    Poisoned,
+}
+
+impl fmt::Display for BlockingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BlockingError::Panicked(message) => write!(f, "Blocking job panicked: {}", message),
+            BlockingError::Poisoned => write!(f, "Blocking job state is poisoned"),
+        }
+    }
+}
+
+struct BlockingState<T> {
+    output: Option<Result<T, BlockingError>>,
+    waker: Option<Waker>,
+}
+
+pub struct BlockingTask<T> {
+    state: Arc<Mutex<BlockingState<T>>>,
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = Result<T, BlockingError>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(_) => return Poll::Ready(Err(BlockingError::Poisoned)),
+        };
+        match state.output.take() {
+            Some(output) => Poll::Ready(output),
+            None => {
+                state.waker = Some(context.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+pub fn spawn_blocking<T: Send + 'static>(job: impl FnOnce() -> T + Send + 'static) -> BlockingTask<T> {
+    let state = Arc::new(Mutex::new(BlockingState {
+        output: None,
+        waker: None,
+    }));
+    let job_state = state.clone();
+    BLOCKING_POOL.execute(Box::new(move || {
+        let output = panic::catch_unwind(AssertUnwindSafe(job))
+            .map_err(|payload| BlockingError::Panicked(panic_message(payload)));
+        // the output has to reach the task even if the state got poisoned, otherwise it never wakes
+        let waker = {
+            let mut state = job_state.lock().unwrap_or_else(PoisonError::into_inner);
+            state.output = Some(output);
+            state.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }));
+    BlockingTask { state }
+}
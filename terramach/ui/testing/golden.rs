@@ -0,0 +1,234 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::env;
+use std::fmt::{Display as FmtDisplay, Formatter};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use terramach_graphics::image::CachingHint;
+use terramach_graphics::{raster, AlphaType, ColorType, Data, Display, EncodedImageFormat, FontMgr, ISize, Image, ImageInfo, Size, Typeface};
+
+use crate::gpu::{RasterCache, TextureRegistry};
+use crate::testing::WidgetTester;
+use crate::widgets::{DefaultTextStyle, TextStyle};
+use crate::BoxedWidget;
+
+const TEST_FONT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/ui/testing/fonts/DejaVuSans.ttf");
+const UPDATE_GOLDENS_VAR: &str = "TERRAMACH_UPDATE_GOLDENS";
+
+pub fn test_typeface() -> Typeface {
+    let data = fs::read(TEST_FONT).expect("Failed to read a test font");
+    FontMgr::new()
+        .new_from_data(&data, None)
+        .expect("Failed to load a test font")
+}
+
+#[derive(Debug)]
+pub enum GoldenError {
+    Render,
+    Encode,
+    Decode(PathBuf),
+    Missing(PathBuf),
+    Io(io::Error),
+    SizeMismatch {
+        expected: ISize,
+        actual: ISize,
+    },
+    PixelMismatch {
+        pixels: usize,
+        diff: PathBuf,
+    },
+}
+
+impl FmtDisplay for GoldenError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            GoldenError::Render => write!(f, "Failed to render a widget"),
+            GoldenError::Encode => write!(f, "Failed to encode an image"),
+            GoldenError::Decode(path) => write!(f, "Failed to decode a golden image {:?}", path),
+            GoldenError::Missing(path) => write!(
+                f,
+                "Golden image {:?} does not exist, set {} to record it",
+                path, UPDATE_GOLDENS_VAR,
+            ),
+            GoldenError::Io(error) => write!(f, "{}", error),
+            GoldenError::SizeMismatch { expected, actual } => write!(
+                f,
+                "Golden size {}x{} does not match rendered size {}x{}",
+                expected.width, expected.height, actual.width, actual.height,
+            ),
+            GoldenError::PixelMismatch { pixels, diff } => write!(
+                f,
+                "{} pixels do not match golden, see {:?}",
+                pixels, diff,
+            ),
+        }
+    }
+}
+
+impl From<io::Error> for GoldenError {
+    fn from(error: io::Error) -> Self {
+        GoldenError::Io(error)
+    }
+}
+
+pub struct Golden {
+    size: Size,
+    pixel_ratio: f32,
+    tolerance: u8,
+}
+
+impl Golden {
+    pub fn new(size: impl Into<Size>) -> Self {
+        Golden {
+            size: size.into(),
+            pixel_ratio: 1.0,
+            tolerance: 0,
+        }
+    }
+
+    pub fn with_pixel_ratio(mut self, pixel_ratio: f32) -> Self {
+        self.pixel_ratio = pixel_ratio;
+        self
+    }
+
+    pub fn with_tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    pub fn render(&self, content: impl Into<BoxedWidget>) -> Option<Image> {
+        let content = DefaultTextStyle::new(
+            TextStyle::default().with_typeface(test_typeface()),
+            content.into(),
+        );
+        let mut tester = WidgetTester::new(content)
+            .with_size(self.size)
+            .with_pixel_ratio(self.pixel_ratio);
+        tester.pump();
+        self.render_tester(&tester)
+    }
+
+    pub fn render_tester(&self, tester: &WidgetTester) -> Option<Image> {
+        let frame = tester.frame()?;
+        let mut display = raster::Display::new(tester.size(), self.pixel_ratio);
+        let snapshot = display.snapshot();
+        let mut surface = display.new_surface()?;
+        let canvas = surface.canvas();
+        canvas.clear(0);
+        frame.draw(canvas, &mut TextureRegistry::new(), &mut RasterCache::new());
+        canvas.flush();
+        display.present_current();
+        snapshot.image()
+    }
+
+    pub fn render_png(&self, content: impl Into<BoxedWidget>) -> Option<Vec<u8>> {
+        encode_png(&self.render(content)?)
+    }
+
+    pub fn compare(
+        &self,
+        content: impl Into<BoxedWidget>,
+        golden: impl AsRef<Path>,
+    ) -> Result<(), GoldenError> {
+        let image = self.render(content).ok_or(GoldenError::Render)?;
+        self.compare_image(&image, golden)
+    }
+
+    pub fn compare_image(&self, image: &Image, golden: impl AsRef<Path>) -> Result<(), GoldenError> {
+        let golden = golden.as_ref();
+        if env::var_os(UPDATE_GOLDENS_VAR).is_some() {
+            if let Some(parent) = golden.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(golden, encode_png(image).ok_or(GoldenError::Encode)?)?;
+            return Ok(());
+        }
+        if !golden.exists() {
+            return Err(GoldenError::Missing(golden.to_path_buf()));
+        }
+
+        let expected = Image::from_encoded(Data::new_copy(&fs::read(golden)?), None)
+            .ok_or_else(|| GoldenError::Decode(golden.to_path_buf()))?;
+        if expected.dimensions() != image.dimensions() {
+            return Err(GoldenError::SizeMismatch {
+                expected: expected.dimensions(),
+                actual: image.dimensions(),
+            });
+        }
+
+        let expected_pixels = read_pixels(&expected).ok_or_else(|| GoldenError::Decode(golden.to_path_buf()))?;
+        let actual_pixels = read_pixels(image).ok_or(GoldenError::Render)?;
+        let mut diff_pixels = Vec::with_capacity(actual_pixels.len());
+        let mut mismatched = 0;
+        for (expected, actual) in expected_pixels.chunks(4).zip(actual_pixels.chunks(4)) {
+            let matches = expected.iter()
+                .zip(actual)
+                .all(|(expected, actual)| {
+                    (*expected as i16 - *actual as i16).abs() <= self.tolerance as i16
+                });
+            if matches {
+                let gray = ((actual[0] as u16 + actual[1] as u16 + actual[2] as u16) / 3 / 4) as u8;
+                diff_pixels.extend_from_slice(&[gray, gray, gray, 0xFF]);
+            } else {
+                mismatched += 1;
+                diff_pixels.extend_from_slice(&[0xFF, 0x00, 0x00, 0xFF]);
+            }
+        }
+        if mismatched == 0 {
+            return Ok(());
+        }
+
+        let diff = golden.with_extension("diff.png");
+        let diff_image = image_from_pixels(image.dimensions(), diff_pixels).ok_or(GoldenError::Encode)?;
+        fs::write(&diff, encode_png(&diff_image).ok_or(GoldenError::Encode)?)?;
+        fs::write(golden.with_extension("actual.png"), encode_png(image).ok_or(GoldenError::Encode)?)?;
+        Err(GoldenError::PixelMismatch {
+            pixels: mismatched,
+            diff,
+        })
+    }
+}
+
+fn pixels_info(dimensions: ISize) -> ImageInfo {
+    ImageInfo::new(dimensions, ColorType::RGBA8888, AlphaType::Unpremul, None)
+}
+
+fn read_pixels(image: &Image) -> Option<Vec<u8>> {
+    let info = pixels_info(image.dimensions());
+    let row_bytes = info.min_row_bytes();
+    let mut pixels = vec![0; row_bytes * info.height() as usize];
+    if image.read_pixels(&info, pixels.as_mut_slice(), row_bytes, (0, 0), CachingHint::Allow) {
+        Some(pixels)
+    } else {
+        None
+    }
+}
+
+fn image_from_pixels(dimensions: ISize, pixels: Vec<u8>) -> Option<Image> {
+    let info = pixels_info(dimensions);
+    let row_bytes = info.min_row_bytes();
+    Image::from_raster_data(&info, Data::new_copy(&pixels), row_bytes)
+}
+
+fn encode_png(image: &Image) -> Option<Vec<u8>> {
+    let data = image.encode_to_data(EncodedImageFormat::PNG)?;
+    Some(data.as_bytes().to_vec())
+}
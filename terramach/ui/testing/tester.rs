@@ -0,0 +1,247 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::cell::Ref;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use terramach_graphics::{Point, Rect, Size};
+
+use flagset::FlagSet;
+
+#[cfg(feature = "inspector")]
+use serde_json::Value;
+
+use time_point::TimePoint;
+
+use crate::gpu::{DetachedPipeline, Frame};
+use crate::platform::SharedRunLoop;
+use crate::widgets::Text;
+use crate::{AppEvent, AppEventDispatcher, BoxedWidget, DebugPaint, HitKey, Id, ManualClock, RenderTree, Touch, TouchId, Widget, WidgetContext};
+
+const FRAME_INTERVAL: Duration = Duration::from_nanos(16_666_667);
+const DRAG_STEPS: usize = 10;
+const TEST_TOUCH_ID: TouchId = 1;
+
+pub struct WidgetTester {
+    tree: RenderTree,
+    pipeline: DetachedPipeline,
+    dispatcher: AppEventDispatcher,
+    size: Size,
+    clock: ManualClock,
+    frame: Option<Frame>,
+}
+
+impl WidgetTester {
+    pub fn new(content: impl Into<BoxedWidget>) -> Self {
+        let pipeline = DetachedPipeline::new();
+        let clock = ManualClock::new();
+        WidgetTester {
+            tree: RenderTree::new(pipeline.share(), content.into()).with_clock(clock.shared()),
+            pipeline,
+            dispatcher: AppEventDispatcher::new(1.0, None),
+            size: Size::new(640.0, 380.0),
+            clock,
+            frame: None,
+        }
+    }
+
+    pub fn with_size(mut self, size: impl Into<Size>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    pub fn with_pixel_ratio(mut self, pixel_ratio: f32) -> Self {
+        self.dispatcher = AppEventDispatcher::new(pixel_ratio, None);
+        self
+    }
+
+    pub fn with_debug_paint(self, flags: impl Into<FlagSet<DebugPaint>>) -> Self {
+        self.tree.debug_paint().set(flags);
+        self
+    }
+
+    pub fn with_run_loop(mut self, run_loop: SharedRunLoop) -> Self {
+        self.tree = self.tree.with_run_loop(run_loop);
+        self
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    pub fn resize(&mut self, size: impl Into<Size>) {
+        self.size = size.into();
+        self.tree.invalidate();
+        self.pump();
+    }
+
+    pub fn clock(&self) -> &ManualClock {
+        &self.clock
+    }
+
+    pub fn frame(&self) -> Option<&Frame> {
+        self.frame.as_ref()
+    }
+
+    pub fn pump(&mut self) {
+        self.tree.render(self.size);
+        if let Some(frame) = self.pipeline.poll_frame() {
+            self.frame = Some(frame);
+        }
+    }
+
+    pub fn pump_for(&mut self, duration: Duration) {
+        let mut remaining = duration;
+        loop {
+            let interval = remaining.min(FRAME_INTERVAL);
+            self.clock.advance(interval);
+            remaining -= interval;
+            if self.tree.needs_frame() {
+                let timestamp = TimePoint::new(self.clock.elapsed().as_nanos() as i64);
+                self.dispatch(vec![AppEvent::Frame(timestamp)]);
+            } else {
+                self.pump();
+            }
+            if remaining.as_nanos() == 0 {
+                break;
+            }
+        }
+    }
+
+    pub fn pump_tasks(&mut self, timeout: Duration) -> bool {
+        let start = Instant::now();
+        loop {
+            self.pump();
+            if !self.tree.has_active_tasks() {
+                self.pump();
+                return true;
+            }
+            if start.elapsed() >= timeout {
+                return false;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    pub fn emit_event(&mut self, event: AppEvent) {
+        self.dispatch(vec![event]);
+    }
+
+    fn dispatch(&mut self, events: Vec<AppEvent>) {
+        self.dispatcher.dispatch(&mut self.tree, events);
+        self.pump();
+    }
+
+    pub fn tap(&mut self, location: impl Into<Point>) {
+        let location = location.into();
+        // a pointer hovers before it presses, gestures rely on that to track the pointer
+        self.emit_event(AppEvent::Hover(location));
+        self.emit_event(AppEvent::TouchBegin(Touch::new(TEST_TOUCH_ID, location)));
+        self.emit_event(AppEvent::TouchEnd(Touch::new(TEST_TOUCH_ID, location)));
+    }
+
+    pub fn tap_widget(&mut self, id: Id) {
+        if let Some(bounds) = self.bounds(id) {
+            self.tap(bounds.center());
+        }
+    }
+
+    pub fn drag(&mut self, from: impl Into<Point>, to: impl Into<Point>) {
+        let from = from.into();
+        let to = to.into();
+        self.emit_event(AppEvent::Hover(from));
+        self.emit_event(AppEvent::TouchBegin(Touch::new(TEST_TOUCH_ID, from)));
+        for step in 1..=DRAG_STEPS {
+            let progress = step as f32 / DRAG_STEPS as f32;
+            let location = from + (to - from) * progress;
+            self.emit_event(AppEvent::TouchUpdate(Touch::new(TEST_TOUCH_ID, location)));
+        }
+        self.emit_event(AppEvent::TouchEnd(Touch::new(TEST_TOUCH_ID, to)));
+    }
+
+    pub fn scroll(&mut self, location: impl Into<Point>, delta: impl Into<Point>) {
+        self.emit_event(AppEvent::Hover(location.into()));
+        self.emit_event(AppEvent::Scroll(delta.into()));
+    }
+
+    pub fn send_key(&mut self, key: HitKey) {
+        self.emit_event(AppEvent::Key(key));
+    }
+
+    pub fn widgets(&self) -> Vec<Id> {
+        let mut widgets = Vec::new();
+        let mut ids = vec![self.tree.root_widget()];
+        while let Some(id) = ids.pop() {
+            widgets.push(id);
+            ids.extend(self.tree.widget_children(id).into_iter().rev());
+        }
+        widgets
+    }
+
+    pub fn find(&self, predicate: impl Fn(&BoxedWidget) -> bool) -> Vec<Id> {
+        self.widgets()
+            .into_iter()
+            .filter(|id| self.tree.widget(*id).map_or(false, |widget| predicate(widget)))
+            .collect()
+    }
+
+    pub fn find_by_type<T: 'static + Widget>(&self) -> Vec<Id> {
+        self.find(|widget| widget.as_any().is::<T>())
+    }
+
+    pub fn find_by_text(&self, text: impl AsRef<str>) -> Vec<Id> {
+        self.find(|widget| {
+            widget.as_any().downcast_ref::<Text>()
+                .map_or(false, |widget| widget.text() == text.as_ref())
+        })
+    }
+
+    pub fn widget<T: 'static + Widget>(&self, id: Id) -> Option<&T> {
+        self.tree.widget(id)?.as_any().downcast_ref::<T>()
+    }
+
+    #[cfg(feature = "inspector")]
+    pub fn inspect(&self) -> Value {
+        self.tree.inspect()
+    }
+
+    #[cfg(feature = "inspector")]
+    pub fn dump(&self) -> String {
+        self.tree.dump()
+    }
+
+    pub fn context(&self, id: Id) -> Option<Ref<WidgetContext>> {
+        self.tree.widget_context(id)
+    }
+
+    pub fn bounds(&self, id: Id) -> Option<Rect> {
+        Some(Rect::from_point_and_size(
+            self.tree.widget_location(id)?,
+            self.tree.widget_size(id)?,
+        ))
+    }
+
+    pub fn overflow(&self, id: Id) -> Option<Size> {
+        self.tree.widget_overflow(id)
+    }
+
+    pub fn baseline(&self, id: Id) -> Option<f32> {
+        self.tree.widget_baseline(id)
+    }
+}
@@ -19,7 +19,7 @@
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
-use crate::{Id, IndexPool};
+use crate::{Clock, Id, IndexPool, SharedClock};
 
 pub struct Timer {
     delay: Option<Duration>,
@@ -54,8 +54,16 @@ struct ScheduledTimer {
 }
 
 impl ScheduledTimer {
-    pub fn fire_in(&self) -> Duration {
-        let elapsed = self.timestamp.elapsed();
+    pub fn new(timer: Timer, now: Instant) -> Self {
+        ScheduledTimer {
+            timer,
+            timestamp: now,
+            ongoing: false,
+        }
+    }
+
+    pub fn fire_in(&self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.timestamp);
         let delay = if self.ongoing {
             self.timer.interval
         } else {
@@ -73,18 +81,18 @@ impl ScheduledTimer {
         self.timer.is_repeated()
     }
 
-    pub fn fire(&mut self) -> bool {
-        let elapsed = self.timestamp.elapsed();
+    pub fn fire(&mut self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.timestamp);
         if self.ongoing {
             if elapsed >= self.timer.interval.unwrap_or_default() {
-                self.timestamp = Instant::now();
+                self.timestamp = now;
                 true
             } else {
                 false
             }
         } else {
             if elapsed >= self.timer.delay.unwrap_or_default() {
-                self.timestamp = Instant::now();
+                self.timestamp = now;
                 self.ongoing = true;
                 true
             } else {
@@ -94,32 +102,28 @@ impl ScheduledTimer {
     }
 }
 
-impl From<Timer> for ScheduledTimer {
-    fn from(timer: Timer) -> Self {
-        ScheduledTimer {
-            timer,
-            timestamp: Instant::now(),
-            ongoing: false,
-        }
-    }
-}
-
 pub struct Timers {
     ids: IndexPool,
     timers: HashMap<Id, ScheduledTimer>,
+    clock: SharedClock,
 }
 
 impl Timers {
-    pub fn new() -> Self {
+    pub fn new(clock: SharedClock) -> Self {
         Timers {
             ids: IndexPool::new(),
             timers: HashMap::new(),
+            clock,
         }
     }
 
+    pub fn clock(&self) -> &SharedClock {
+        &self.clock
+    }
+
     pub fn add(&mut self, timer: Timer) -> Id {
         let id = self.ids.take();
-        self.timers.insert(id, ScheduledTimer::from(timer));
+        self.timers.insert(id, ScheduledTimer::new(timer, self.clock.now()));
         id
     }
 
@@ -140,9 +144,10 @@ impl Timers {
     }
 
     pub fn next_fire_time(&self) -> Option<Duration> {
+        let now = self.clock.now();
         let mut next_time = None;
         for timer in self.timers.values() {
-            let fire_time = timer.fire_in();
+            let fire_time = timer.fire_in(now);
             if let Some(time) = &next_time {
                 if time < &fire_time {
                     next_time = Some(fire_time);
@@ -158,11 +163,12 @@ impl Timers {
         if self.timers.is_empty() {
             return None;
         }
+        let now = self.clock.now();
         let mut fired = Vec::new();
         let ids: Vec<Id> = self.timers.keys().copied().collect();
         for id in ids {
             let timer = self.timers.get_mut(&id)?;
-            if timer.fire() {
+            if timer.fire(now) {
                 if !timer.is_repeated() {
                     self.remove(id);
                 }
@@ -19,33 +19,62 @@
 use std::cell::{Ref, RefCell, RefMut};
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::ops::{Deref, DerefMut};
-use std::panic;
-use std::time::Duration;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::any::Any;
 use std::iter::FromIterator;
+use std::mem;
 
 use crate::*;
-use crate::gpu::{Frame, SharedPipeline, RenderTexture, TextureId};
-use crate::platform::Cursor;
+use crate::gpu::{Frame, FrameSpan, FrameTimings, PerformanceStats, SharedPipeline, RenderTexture, TextureId};
+use crate::platform::{Cursor, SharedRunLoop};
 
-use terramach_graphics::{Point, Size};
+use terramach_graphics::{Point, Rect, Size};
+
+use flagset::FlagSet;
+
+#[cfg(feature = "inspector")]
+use serde_json::{json, Value};
 
 use time_point::TimePoint;
 
+#[cfg(feature = "inspector")]
+pub type InspectorHandler = Box<dyn FnMut(&Value)>;
+
+#[cfg(not(feature = "inspector"))]
+pub enum InspectorHandler {}
+
 pub struct RenderTree {
     tree: Tree<BoxedWidget>,
     root_widget: Id,
     states: HashMap<Id, WidgetState>,
     layer_tree: LayerTree,
     need_paint: bool,
+    paint_version: u64,
+    repaint_boundaries: HashMap<Id, u64>,
     need_build: HashSet<Id>,
+    need_layout: HashSet<Id>,
     requested_frame: HashSet<Id>,
     active_timers: HashSet<Id>,
+    dependencies: HashMap<Id, HashSet<Id>>,
+    dependents: HashMap<Id, HashSet<Id>>,
     pipeline: SharedPipeline,
     texture_ids: IndexPool,
+    clock: SharedClock,
+    clock_origin: Instant,
+    errors: RefCell<Vec<WidgetError>>,
+    intrinsic_dependents: RefCell<HashSet<Id>>,
+    error_handler: Option<ErrorHandler>,
+    debug_paint: DebugPaintSettings,
+    debug_paint_flags: FlagSet<DebugPaint>,
+    timings: FrameTimings,
+    event_wakeup: EventWakeup,
 }
 
 impl RenderTree {
+    const MAX_ERROR_RECOVERIES: usize = 3;
+
     pub fn new(
         pipeline: SharedPipeline,
         root: BoxedWidget,
@@ -54,6 +83,7 @@ impl RenderTree {
         let root_widget = tree.insert(root, None);
         let mut need_build = HashSet::new();
         need_build.insert(root_widget);
+        let clock = SystemClock::shared();
         RenderTree {
             root_widget,
             tree,
@@ -62,16 +92,63 @@ impl RenderTree {
             states: HashMap::new(),
             layer_tree: LayerTree::new(),
             need_paint: false,
+            paint_version: 0,
+            repaint_boundaries: HashMap::new(),
+            need_layout: HashSet::new(),
             requested_frame: HashSet::new(),
             active_timers: HashSet::new(),
+            dependencies: HashMap::new(),
+            dependents: HashMap::new(),
             texture_ids: IndexPool::new(),
+            clock_origin: clock.now(),
+            clock,
+            errors: RefCell::new(Vec::new()),
+            intrinsic_dependents: RefCell::new(HashSet::new()),
+            error_handler: None,
+            debug_paint: DebugPaintSettings::default(),
+            debug_paint_flags: FlagSet::default(),
+            timings: FrameTimings::new(0),
+            event_wakeup: EventWakeup::default(),
         }
     }
 
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock_origin = clock.now();
+        self.clock = clock;
+        self
+    }
+
+    pub fn with_debug_paint(mut self, debug_paint: DebugPaintSettings) -> Self {
+        self.debug_paint = debug_paint;
+        self
+    }
+
+    pub fn debug_paint(&self) -> DebugPaintSettings {
+        self.debug_paint.clone()
+    }
+
+    pub fn with_error_handler(mut self, error_handler: impl Into<Option<ErrorHandler>>) -> Self {
+        self.error_handler = error_handler.into();
+        self
+    }
+
+    pub fn with_run_loop(mut self, run_loop: SharedRunLoop) -> Self {
+        self.event_wakeup = EventWakeup::new(run_loop);
+        self
+    }
+
+    pub fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
     pub fn needs_frame(&self) -> bool {
         !self.requested_frame.is_empty()
     }
 
+    pub fn has_active_tasks(&self) -> bool {
+        self.states.values().any(|state| state.context().has_active_tasks())
+    }
+
     pub fn invalidate(&mut self) {
         for state in self.states.values_mut() {
             state.set_need_paint(true);
@@ -79,6 +156,17 @@ impl RenderTree {
         }
     }
 
+    fn invalidate_debug_paint(&mut self) {
+        let debug_paint_flags = self.debug_paint.flags();
+        if debug_paint_flags != self.debug_paint_flags {
+            self.debug_paint_flags = debug_paint_flags;
+            for state in self.states.values_mut() {
+                state.set_need_paint(true);
+            }
+            self.need_paint = true;
+        }
+    }
+
     fn invalidate_requests(&mut self, id: Id) {
         if let Some(state) = self.states.get(&id) {
             let context = state.context();
@@ -96,8 +184,15 @@ impl RenderTree {
     fn emit_event_direct(&mut self, id: Id, event: Event) -> bool {
         if let Some(widget) = self.tree.node(id) {
             if let Some(state) = self.states.get_mut(&id) {
+                let callback = match &event {
+                    Event::Callback(callback) => Some(callback.clone()),
+                    _ => None,
+                };
                 let mut event_context = EventContext::new(event);
-                widget.event(state.context_mut().deref_mut(), &mut event_context);
+                match callback {
+                    Some(callback) => callback.invoke(state.context_mut().deref_mut(), &mut event_context),
+                    None => widget.event(state.context_mut().deref_mut(), &mut event_context),
+                }
                 if event_context.need_build() {
                     self.invalidate_build(id);
                 } else if event_context.need_layout() {
@@ -134,12 +229,18 @@ impl RenderTree {
             }
         } else {
             match event {
-                Event::Frame(timestamp) => self.emit_frame_event(timestamp),
+                // frames are stamped with the tree clock, a platform timestamp only paces them
+                Event::Frame(_) => self.emit_frame_event(self.frame_timestamp()),
                 _ => panic!("{:?} cannot be issued to all widgets", event),
             }
         }
     }
 
+    fn frame_timestamp(&self) -> TimePoint {
+        let elapsed = self.clock.now().saturating_duration_since(self.clock_origin);
+        TimePoint::new(elapsed.as_nanos() as i64)
+    }
+
     fn emit_frame_event(&mut self, timestamp: TimePoint) {
         if self.requested_frame.is_empty() {
             return;
@@ -153,6 +254,14 @@ impl RenderTree {
         }
     }
 
+    fn flush_pending_events(&mut self) {
+        for id in self.event_wakeup.take_pending() {
+            if self.states.contains_key(&id) {
+                self.emit_widget_events(id);
+            }
+        }
+    }
+
     fn flush_pending_timers(&mut self) {
         if self.active_timers.is_empty() {
             return;
@@ -255,6 +364,108 @@ impl RenderTree {
         None
     }
 
+    pub fn layer_tree(&self) -> &LayerTree {
+        &self.layer_tree
+    }
+
+    #[cfg(feature = "inspector")]
+    pub fn inspect(&self) -> Value {
+        json!({
+            "widgets": self.inspect_widget(self.root_widget),
+            "layers": self.layer_tree.inspect(),
+        })
+    }
+
+    #[cfg(feature = "inspector")]
+    pub fn dump(&self) -> String {
+        serde_json::to_string_pretty(&self.inspect()).unwrap_or_default()
+    }
+
+    #[cfg(feature = "inspector")]
+    fn inspect_widget(&self, id: Id) -> Value {
+        let widget = match self.tree.node(id) {
+            Some(widget) => widget,
+            None => return Value::Null,
+        };
+        let children = self.tree
+            .children(id)
+            .map(|children| children.iter().map(|child| self.inspect_widget(*child)).collect())
+            .unwrap_or_default();
+        let mut node = json!({
+            "id": id,
+            "type": widget.type_name(),
+            "children": Value::Array(children),
+        });
+        if let Some(state) = self.states.get(&id) {
+            let context = state.context();
+            let mut timers = context.timers_ids.keys().copied().collect::<Vec<usize>>();
+            timers.sort();
+            let mut tasks = context.tasks.ids();
+            tasks.sort();
+            node["size"] = state.size().map_or(Value::Null, inspect_size);
+            node["offset"] = state.offset().map_or(Value::Null, inspect_point);
+            node["constraints"] = state.constraints().map_or(Value::Null, |constraints| {
+                json!({
+                    "minimum": inspect_size(constraints.minimum_size()),
+                    "maximum": inspect_size(constraints.maximum_size()),
+                })
+            });
+            node["need_build"] = state.need_build().into();
+            node["need_layout"] = state.need_layout().into();
+            node["need_paint"] = state.need_paint().into();
+            node["active_timers"] = timers.into();
+            node["active_tasks"] = tasks.into();
+            node["texture"] = state.texture().map_or(Value::Null, |texture| texture.into());
+        }
+        node
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn root_widget(&self) -> Id {
+        self.root_widget
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn widget(&self, id: Id) -> Option<&BoxedWidget> {
+        self.tree.node(id)
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn widget_children(&self, id: Id) -> Vec<Id> {
+        self.tree.children(id).cloned().unwrap_or_default()
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn widget_context(&self, id: Id) -> Option<Ref<WidgetContext>> {
+        Some(self.states.get(&id)?.context())
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn widget_size(&self, id: Id) -> Option<Size> {
+        self.states.get(&id)?.size()
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn widget_baseline(&self, id: Id) -> Option<f32> {
+        self.states.get(&id)?.baseline()
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn widget_overflow(&self, id: Id) -> Option<Size> {
+        Some(self.states.get(&id)?.overflow())
+    }
+
+    #[cfg(feature = "testing")]
+    pub(crate) fn widget_location(&self, id: Id) -> Option<Point> {
+        let mut location = self.states.get(&id)?.offset().unwrap_or_default();
+        let mut parent = self.tree.parent(id);
+        while let Some(id) = parent {
+            location += self.states.get(&id)?.offset().unwrap_or_default();
+            parent = self.tree.parent(id);
+        }
+        Some(location)
+    }
+
     fn invalidate_paint(&mut self, id: impl Into<Option<Id>>) {
         let id = id.into().unwrap_or(self.root_widget);
         if let Some(state) = self.states.get_mut(&id) {
@@ -277,25 +488,105 @@ impl RenderTree {
             state.set_need_layout(true);
             state.set_need_paint(true);
             self.need_paint = true;
-            if let Some(parent) = self.tree.parent(id) {
+            if state.relayout_boundary() {
+                self.need_layout.insert(id);
+            } else if let Some(parent) = self.tree.parent(id) {
                 self.invalidate_layout(parent);
             }
         }
     }
 
+    fn is_error_boundary(&self, id: Id) -> bool {
+        self.tree.node(id).map_or(false, |widget| widget.error_boundary())
+    }
+
+    fn error_boundary_of(&self, id: Id) -> Option<Id> {
+        let mut current = Some(id);
+        while let Some(id) = current {
+            if self.is_error_boundary(id) {
+                return Some(id);
+            }
+            current = self.tree.parent(id);
+        }
+        None
+    }
+
+    /// Runs a phase entry point and hands a panic to the nearest error boundary
+    /// at or above `id`. Panics with no boundary above them are resumed.
+    fn catch_phase<R>(&mut self, id: Id, phase: WidgetPhase, f: impl FnOnce(&mut Self) -> R) -> Option<R> {
+        match panic::catch_unwind(AssertUnwindSafe(|| f(self))) {
+            Ok(result) => Some(result),
+            Err(payload) => match self.error_boundary_of(id) {
+                Some(boundary) => {
+                    self.report_error(boundary, phase, payload);
+                    None
+                }
+                None => panic::resume_unwind(payload),
+            },
+        }
+    }
+
+    fn report_error(&self, id: Id, phase: WidgetPhase, payload: Box<dyn Any + Send>) {
+        self.errors.borrow_mut().push(WidgetError::from_panic(id, phase, payload));
+    }
+
+    fn recover_errors(&mut self) -> Option<WidgetPhase> {
+        let errors = self.errors.replace(Vec::new());
+        let phase = errors.last().map(|error| error.phase());
+        for error in errors {
+            if let Some(error_handler) = &mut self.error_handler {
+                error_handler(&error);
+            }
+            let id = error.widget();
+            if self.tree.node(id).is_none() {
+                continue;
+            }
+            for child in self.tree.children(id).cloned().unwrap_or_default() {
+                self.remove_widget(child);
+            }
+            if let Some(state) = self.states.get(&id) {
+                state.context_mut().error = Some(error);
+            }
+            self.invalidate_build(id);
+        }
+        phase
+    }
+
+    fn report_dropped_frame(&mut self, phase: WidgetPhase) {
+        let error = WidgetError::new(
+            self.root_widget,
+            phase,
+            format!("Frame dropped after {} error recoveries", RenderTree::MAX_ERROR_RECOVERIES),
+        );
+        if let Some(error_handler) = &mut self.error_handler {
+            error_handler(&error);
+        }
+    }
+
     fn build_widget(&mut self, id: Id) {
+        if !self.is_error_boundary(id) {
+            return self.build_widget_inner(id);
+        }
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(|| self.build_widget_inner(id))) {
+            self.report_error(id, WidgetPhase::Build, payload);
+        }
+    }
+
+    fn build_widget_inner(&mut self, id: Id) {
         if let Some(state) = self.states.get(&id) {
             if !state.need_build() {
                 return;
             }
         } else {
-            self.states.insert(id, WidgetState::new());
+            self.states.insert(id, WidgetState::new(id, self.clock.clone(), self.event_wakeup.clone()));
         }
+        self.timings.add_widgets_built(1);
         let widget = self.tree.node(id).unwrap();
         let state = self.states.get_mut(&id).unwrap();
-        if state.mounted() {
+        let dependencies = if state.mounted() {
             let mut update = UpdateContext::new(id, &self.tree);
             widget.update(state.context_mut().deref_mut(), &mut update);
+            update.dependencies
         } else {
             state.set_mounted(true);
             let mut mount = MountContext::new(
@@ -306,10 +597,12 @@ impl RenderTree {
             );
             widget.mount(state.context_mut().deref_mut(), &mut mount);
             state.set_texture(mount.texture);
-        }
+            mount.dependencies
+        };
 
         let event_emitter = state.events_mut().emitter();
-        let mut build = BuildContext::new(event_emitter);
+        let event_sender = state.context().event_sender();
+        let mut build = BuildContext::new(event_emitter, event_sender);
         widget.build(state.context_mut().deref_mut(), &mut build);
         state.set_need_build(false);
         state.set_need_layout(true);
@@ -317,37 +610,128 @@ impl RenderTree {
 
         self.need_paint = true;
         self.invalidate_requests(id);
+        self.set_dependencies(id, dependencies);
 
-        let old_children = self.tree.children(id).cloned();
-        let mut index = 0;
-        for new_child in build.children {
-            let old_child_id = if let Some(old_children) = &old_children {
-                old_children.get(index)
+        let old_children = self.tree.children(id).cloned().unwrap_or_default();
+        let mut keyed_children: HashMap<WidgetKey, Id> = HashMap::new();
+        for old_child_id in &old_children {
+            if let Some(key) = self.tree.node(*old_child_id).and_then(|child| child.key()) {
+                keyed_children.insert(key.clone(), *old_child_id);
+            }
+        }
+
+        let mut reused_children = HashSet::new();
+        let mut children = Vec::new();
+        for (index, new_child) in build.children.into_iter().enumerate() {
+            let old_child_id = if let Some(key) = new_child.key() {
+                keyed_children.get(key).copied()
             } else {
-                None
+                old_children.get(index).copied().filter(|old_child_id| {
+                    self.tree.node(*old_child_id).map_or(false, |child| child.key().is_none())
+                })
             };
-            index += 1;
+            let old_child_id = old_child_id
+                .filter(|old_child_id| !reused_children.contains(old_child_id))
+                .filter(|old_child_id| {
+                    self.tree.node(*old_child_id).map_or(false, |child| new_child.same(child))
+                });
 
             if let Some(old_child_id) = old_child_id {
-                let old_child = self.tree.node(*old_child_id).unwrap();
-                if new_child.same(old_child) {
-                    self.tree.replace(*old_child_id, new_child);
-                    let state = self.states.get_mut(old_child_id).unwrap();
+                reused_children.insert(old_child_id);
+                children.push(old_child_id);
+                let changed = !new_child.same_content(self.tree.node(old_child_id).unwrap());
+                self.tree.replace(old_child_id, new_child);
+                if changed {
+                    let state = self.states.get_mut(&old_child_id).unwrap();
                     state.set_need_build(true);
-                    self.build_widget(*old_child_id);
-                    continue;
+                    state.context_mut().error = None;
+                    self.invalidate_dependents(old_child_id);
                 }
-                self.tree.remove(*old_child_id);
-                if let Some(state) = self.states.remove(old_child_id) {
-                    if let Some(texture) = state.texture() {
-                        self.pipeline.unregister_texture(texture);
-                        self.texture_ids.give(texture);
+                self.build_widget(old_child_id);
+            } else {
+                let child_id = self.tree.insert(new_child, id);
+                children.push(child_id);
+                self.build_widget(child_id);
+            }
+        }
+
+        for old_child_id in old_children {
+            if !reused_children.contains(&old_child_id) {
+                self.remove_widget(old_child_id);
+            }
+        }
+        self.tree.reorder(id, &children);
+    }
+
+    fn set_dependencies(&mut self, id: Id, dependencies: HashSet<Id>) {
+        self.clear_dependencies(id);
+        for ancestor in &dependencies {
+            self.dependents.entry(*ancestor).or_default().insert(id);
+        }
+        if !dependencies.is_empty() {
+            self.dependencies.insert(id, dependencies);
+        }
+    }
+
+    fn clear_dependencies(&mut self, id: Id) {
+        if let Some(dependencies) = self.dependencies.remove(&id) {
+            for ancestor in dependencies {
+                if let Some(dependents) = self.dependents.get_mut(&ancestor) {
+                    dependents.remove(&id);
+                    if dependents.is_empty() {
+                        self.dependents.remove(&ancestor);
                     }
                 }
             }
+        }
+    }
 
-            let child_id = self.tree.insert(new_child, id);
-            self.build_widget(child_id);
+    fn invalidate_dependents(&mut self, id: Id) {
+        if let Some(dependents) = self.dependents.get(&id).cloned() {
+            for dependent in dependents {
+                self.invalidate_build(dependent);
+            }
+        }
+    }
+
+    fn remove_widget(&mut self, id: Id) {
+        let mut subtree = Vec::new();
+        self.collect_subtree(id, &mut subtree);
+        for id in &subtree {
+            self.unmount_widget(*id);
+        }
+        self.tree.remove_all(id);
+    }
+
+    fn collect_subtree(&self, id: Id, subtree: &mut Vec<Id>) {
+        if let Some(children) = self.tree.children(id) {
+            for child in children {
+                self.collect_subtree(*child, subtree);
+            }
+        }
+        subtree.push(id);
+    }
+
+    fn unmount_widget(&mut self, id: Id) {
+        self.need_build.remove(&id);
+        self.need_layout.remove(&id);
+        self.repaint_boundaries.remove(&id);
+        self.requested_frame.remove(&id);
+        self.active_timers.remove(&id);
+        self.clear_dependencies(id);
+        self.dependents.remove(&id);
+        if let Some(state) = self.states.remove(&id) {
+            if state.mounted() {
+                if let Some(widget) = self.tree.node(id) {
+                    widget.unmount(state.context_mut().deref_mut());
+                }
+            }
+            state.context_mut().cancel_all_timers();
+            state.context_mut().cancel_all_tasks();
+            if let Some(texture) = state.texture() {
+                self.pipeline.unregister_texture(texture);
+                self.texture_ids.give(texture);
+            }
         }
     }
 
@@ -355,43 +739,230 @@ impl RenderTree {
         &self,
         id: Id,
         constraints: Constraints,
-        results: &mut HashMap<Id, (Size, Option<Point>)>,
-    ) -> Option<Size> {
+        results: &mut HashMap<Id, LayoutResult>,
+    ) -> Option<(Size, Option<f32>)> {
+        if !self.is_error_boundary(id) {
+            return self.layout_widget_inner(id, constraints, results);
+        }
+        let layout = panic::catch_unwind(AssertUnwindSafe(|| {
+            self.layout_widget_inner(id, constraints, results)
+        }));
+        match layout {
+            Ok(layout) => layout,
+            Err(payload) => {
+                self.report_error(id, WidgetPhase::Layout, payload);
+                let size = constraints.minimum_size();
+                results.insert(id, LayoutResult::new(size, constraints, None));
+                Some((size, None))
+            }
+        }
+    }
+
+    fn layout_widget_inner(
+        &self,
+        id: Id,
+        constraints: Constraints,
+        results: &mut HashMap<Id, LayoutResult>,
+    ) -> Option<(Size, Option<f32>)> {
         let state = self.states.get(&id)?;
-        if !state.need_layout() {
+        if !state.need_layout() && state.constraints() == Some(constraints) {
             if let Some(size) = state.size() {
-                results.insert(id, (size, None));
-                return Some(size);
+                results.insert(id, LayoutResult::new(size, None, state.baseline()));
+                return Some((size, state.baseline()));
             }
         }
 
         let child_id = |index: usize| -> Option<Id> { self.tree.children(id)?.get(index).copied() };
-        let mut layout_child = |id: Id, child_constraints: &Constraints| -> Option<Size> {
+        let mut layout_child = |id: Id, child_constraints: &Constraints| -> Option<(Size, Option<f32>)> {
             self.layout_widget(id, *child_constraints, results)
         };
+        let child_intrinsic = |index: usize, dimension: IntrinsicDimension, extent: f32| -> f32 {
+            child_id(index).map_or(0.0, |child| self.intrinsic_dimension(child, dimension, extent))
+        };
         let mut layout = LayoutContext::new(
             constraints,
             self.tree.child_count(id),
             &child_id,
             &mut layout_child,
+            &child_intrinsic,
         );
 
         let widget = self.tree.node(id)?;
         let size = widget.layout(state.context_mut().deref_mut(), &mut layout);
+        let baseline = layout.baseline.or_else(|| {
+            (0..layout.child_count()).find_map(|index| {
+                let baseline = layout.child_baseline(index)?;
+                let offset = layout
+                    .child_offsets
+                    .get(&child_id(index)?)
+                    .map_or(0.0, |offset| offset.y);
+                Some(baseline + offset)
+            })
+        });
         for (id, offset) in layout.child_offsets {
-            results.get_mut(&id).expect("A child is not laid out").1 = Some(offset);
+            results.get_mut(&id).expect("A child is not laid out").offset = Some(offset);
+        }
+        results.insert(id, LayoutResult::new(size, constraints, baseline));
+        Some((size, baseline))
+    }
+
+    fn intrinsic_dimension(&self, id: Id, dimension: IntrinsicDimension, extent: f32) -> f32 {
+        let (widget, state) = match (self.tree.node(id), self.states.get(&id)) {
+            (Some(widget), Some(state)) => (widget, state),
+            _ => return 0.0,
+        };
+        self.intrinsic_dependents.borrow_mut().insert(id);
+        let child_intrinsic = |index: usize, dimension: IntrinsicDimension, extent: f32| -> f32 {
+            self.tree
+                .children(id)
+                .and_then(|children| children.get(index))
+                .map_or(0.0, |child| self.intrinsic_dimension(*child, dimension, extent))
+        };
+        let intrinsic = IntrinsicContext::new(self.tree.child_count(id), &child_intrinsic);
+        let context = state.context();
+        match dimension {
+            IntrinsicDimension::MinWidth => widget.min_intrinsic_width(&context, &intrinsic, extent),
+            IntrinsicDimension::MaxWidth => widget.max_intrinsic_width(&context, &intrinsic, extent),
+            IntrinsicDimension::MinHeight => widget.min_intrinsic_height(&context, &intrinsic, extent),
+            IntrinsicDimension::MaxHeight => widget.max_intrinsic_height(&context, &intrinsic, extent),
+        }
+    }
+
+    fn layout_subtree(&mut self, id: Id, constraints: Constraints) {
+        let mut results = HashMap::new();
+        self.layout_widget(id, constraints, &mut results);
+        if let Some(result) = results.get_mut(&id) {
+            result.offset = self.states.get(&id).and_then(|state| state.offset());
+        }
+
+        // an ancestor measured these widgets, so their layout changes must reach it
+        let intrinsic_dependents = self.intrinsic_dependents.replace(HashSet::new());
+        for id in &intrinsic_dependents {
+            if let Some(state) = self.states.get_mut(id) {
+                state.set_relayout_boundary(false);
+            }
+        }
+
+        let mut laid_out = Vec::new();
+        for (id, result) in results {
+            let relayout_boundary = self.tree.node(id).map_or(false, |widget| widget.relayout_boundary());
+            if let Some(state) = self.states.get_mut(&id) {
+                state.set_size(result.size);
+                state.set_offset(result.offset);
+                state.set_baseline(result.baseline);
+                if let Some(constraints) = result.constraints {
+                    self.timings.add_widgets_laid_out(1);
+                    state.set_constraints(constraints);
+                    state.set_relayout_boundary(
+                        (constraints.is_tight() || relayout_boundary) && !intrinsic_dependents.contains(&id),
+                    );
+                    state.set_need_paint(true);
+                    self.need_paint = true;
+                    laid_out.push(id);
+                }
+            }
+        }
+
+        // children are placed only after their parent is laid out, so measure overflow last
+        for id in laid_out {
+            let children = self.children_bounds(id);
+            if let Some(state) = self.states.get_mut(&id) {
+                let size = state.size().unwrap_or_default();
+                state.set_overflow(layout_overflow(size, &children));
+            }
+        }
+    }
+
+    fn children_bounds(&self, id: Id) -> Vec<Rect> {
+        self.tree
+            .children(id)
+            .cloned()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|child| {
+                let state = self.states.get(&child)?;
+                Some(Rect::from_point_and_size(state.offset().unwrap_or_default(), state.size()?))
+            })
+            .collect()
+    }
+
+    fn relayout_boundary(&mut self, id: Id) {
+        let (constraints, size, baseline) = match self.states.get(&id) {
+            Some(state) => (state.constraints(), state.size(), state.baseline()),
+            None => return,
+        };
+        if let Some(constraints) = constraints {
+            self.layout_subtree(id, constraints);
+        }
+        let changed = self.states.get(&id).map_or(false, |state| {
+            state.size() != size || state.baseline() != baseline
+        });
+        if changed {
+            if let Some(parent) = self.tree.parent(id) {
+                self.invalidate_layout(parent);
+            }
         }
-        results.insert(id, (size, None));
-        Some(size)
     }
 
     pub fn render(&mut self, size: impl Into<Size>) {
+        self.flush_pending_events();
         self.flush_pending_timers();
+        self.invalidate_debug_paint();
+
+        let size = size.into();
+        let mut failed = None;
+        for _ in 0..RenderTree::MAX_ERROR_RECOVERIES {
+            let build = FrameSpan::enter("build");
+            self.build_pending();
+            self.timings.add_build(build.finish());
+            failed = self.recover_errors();
+            if failed.is_some() {
+                continue;
+            }
 
-        if !self.need_build.is_empty() {
+            let layout = FrameSpan::enter("layout");
+            self.layout_pending(size);
+            self.timings.add_layout(layout.finish());
+            failed = self.recover_errors();
+            if failed.is_some() {
+                continue;
+            }
+
+            if self.need_paint {
+                self.need_paint = false;
+                let paint = FrameSpan::enter("paint");
+                let root_widget = self.root_widget;
+                let nodes = self.catch_phase(root_widget, WidgetPhase::Paint, |tree| tree.paint_widget(root_widget));
+                self.timings.add_paint(paint.finish());
+                failed = self.recover_errors();
+                if failed.is_some() {
+                    continue;
+                }
+                let (nodes, _, _) = nodes.unwrap_or_default();
+                self.layer_tree = LayerTree::with_nodes(nodes);
+
+                let frame_clone = FrameSpan::enter("frame_clone");
+                let frame = Frame::new(&self.layer_tree);
+                let next_timings = FrameTimings::new(self.timings.frame() + 1);
+                let mut timings = mem::replace(&mut self.timings, next_timings);
+                timings.set_frame_clone(frame_clone.finish());
+                self.pipeline.submit_frame(frame.with_timings(timings));
+            }
+            break;
+        }
+        if let Some(phase) = failed {
+            self.report_dropped_frame(phase);
+        }
+    }
+
+    fn build_pending(&mut self) {
+        while !self.need_build.is_empty() {
             for id in self.need_build.drain().collect::<Vec<Id>>() {
+                if self.tree.node(id).is_none() {
+                    continue;
+                }
                 self.invalidate_layout(id);
-                self.build_widget(id);
+                self.catch_phase(id, WidgetPhase::Build, |tree| tree.build_widget(id));
             }
         }
 
@@ -399,89 +970,145 @@ impl RenderTree {
             self.tree.len() == self.states.len(),
             "Render tree is corrupted after a build"
         );
+    }
 
-        let mut results = HashMap::new();
-        self.layout_widget(
-            self.root_widget,
-            Constraints::new_tight(size.into()),
-            &mut results,
-        );
-
-        for (id, (size, offset)) in results {
-            if let Some(state) = self.states.get_mut(&id) {
-                state.set_size(size);
-                state.set_offset(offset);
+    fn layout_pending(&mut self, size: Size) {
+        let root_widget = self.root_widget;
+        self.catch_phase(root_widget, WidgetPhase::Layout, |tree| {
+            tree.layout_subtree(root_widget, Constraints::new_tight(size))
+        });
+        while !self.need_layout.is_empty() {
+            for id in self.need_layout.drain().collect::<Vec<Id>>() {
+                self.catch_phase(id, WidgetPhase::Layout, |tree| tree.relayout_boundary(id));
             }
         }
-
-        if self.need_paint {
-            self.need_paint = false;
-            self.paint_widget(self.root_widget);
-            self.pipeline.submit_frame(Frame::new(&self.layer_tree));
-        }
     }
 
-    fn widget_parent_layer(&self, id: Id) -> Option<Id> {
-        let parent_id = self.tree.parent(id)?;
-        if let Some(layer_id) = self.layer_tree.parent_key_layer(parent_id) {
-            Some(layer_id)
-        } else {
-            self.widget_parent_layer(parent_id)
+    fn paint_widget(&mut self, id: Id) -> (Vec<SharedLayerNode>, bool, bool) {
+        if !self.is_error_boundary(id) {
+            return self.paint_widget_inner(id);
+        }
+        match panic::catch_unwind(AssertUnwindSafe(|| self.paint_widget_inner(id))) {
+            Ok(paint) => paint,
+            Err(payload) => {
+                self.report_error(id, WidgetPhase::Paint, payload);
+                (Vec::new(), true, false)
+            }
         }
     }
 
-    fn paint_widget(&mut self, id: Id) {
-        if let Some(state) = self.states.get(&id) {
-            let size = if let Some(size) = state.size() {
-                size
-            } else {
-                debug_assert!(false, "Widget needs to be laid out before painting!");
-                return;
-            };
-
-            self.layer_tree.drop_key_layer(id);
+    fn paint_widget_inner(&mut self, id: Id) -> (Vec<SharedLayerNode>, bool, bool) {
+        let state = if let Some(state) = self.states.get(&id) {
+            state
+        } else {
+            return (Vec::new(), false, false);
+        };
+        let size = if let Some(size) = state.size() {
+            size
+        } else {
+            debug_assert!(false, "Widget needs to be laid out before painting!");
+            return (Vec::new(), false, false);
+        };
 
-            if state.need_paint() {
-                let mut paint = PaintContext::new(size);
-                if let Some(widget) = self.tree.node(id) {
-                    widget.paint(state.context_mut().deref_mut(), &mut paint);
-                    self.invalidate_requests(id);
-                }
-                let state = self.states.get_mut(&id).unwrap();
-                state.set_need_paint(false);
-                state.set_layer(paint.layers().cloned());
-                state.set_leaf_layer(paint.leaf_layers().cloned());
+        let mut changed = false;
+        if state.need_paint() {
+            changed = true;
+            self.timings.add_widgets_painted(1);
+            let mut paint = PaintContext::new(size);
+            if let Some(widget) = self.tree.node(id) {
+                widget.paint(state.context_mut().deref_mut(), &mut paint);
+                self.invalidate_requests(id);
             }
+            let state = self.states.get_mut(&id).unwrap();
+            state.set_need_paint(false);
+            state.set_paint_count(state.paint_count() + 1);
+            state.set_layer(paint.take_layers());
+            state.set_leaf_layer(paint.take_leaf_layers());
+        }
 
-            let mut parent_layer_id = self.widget_parent_layer(id);
-
-            let state = self.states.get(&id).unwrap();
-            if let Some(offset) = state.offset() {
-                let id = self.layer_tree.insert(
-                    id,
-                    size,
-                    Box::new(OffsetLayer::new(offset)),
-                    parent_layer_id,
-                );
-                parent_layer_id = Some(id);
+        let mut has_textures = self.states[&id].texture().is_some();
+        let mut children_nodes = Vec::new();
+        if let Some(children) = self.tree.children(id).cloned() {
+            for child in children {
+                let (child_nodes, child_changed, child_has_textures) = self.paint_widget(child);
+                children_nodes.extend(child_nodes);
+                changed |= child_changed;
+                has_textures |= child_has_textures;
             }
+        }
 
-            if let Some(layer) = state.layer() {
-                self.layer_tree.insert(id, size, layer.clone_boxed(), parent_layer_id);
+        let state = self.states.get(&id).unwrap();
+        let offset = state.offset();
+        if !changed {
+            if let Some(nodes) = state.layer_nodes(offset) {
+                return (nodes.clone(), false, has_textures);
             }
+        }
 
-            if let Some(children) = self.tree.children(id).cloned() {
-                for child in children {
-                    self.paint_widget(child);
-                }
+        let mut nodes = Vec::new();
+        if let Some(layer) = state.layer() {
+            nodes.push(LayerNode::new(layer.clone(), size, children_nodes));
+        } else {
+            nodes.extend(children_nodes);
+        }
+        if let Some(layer) = state.leaf_layer() {
+            nodes.push(LayerNode::new(layer.clone(), size, None));
+        }
+        if self.debug_paint_flags.bits() != 0 {
+            let painted = state.layer().is_some() || state.leaf_layer().is_some();
+            let children = self.children_bounds(id);
+            let paint_count = if painted { Some(state.paint_count()) } else { None };
+            if let Some(layer) = paint_debug_overlay(self.debug_paint_flags, size, &children, state.overflow(), paint_count) {
+                nodes.push(LayerNode::new(Arc::new(layer), size, None));
             }
+        }
 
-            let state = self.states.get(&id).unwrap();
-            if let Some(layer) = state.leaf_layer() {
-                self.layer_tree
-                    .insert_leaf(id, size, layer.clone_boxed(), parent_layer_id);
+        let repaint_boundary = self.tree.node(id).map_or(false, |widget| widget.repaint_boundary());
+        if repaint_boundary {
+            if changed || !self.repaint_boundaries.contains_key(&id) {
+                self.paint_version += 1;
+                self.repaint_boundaries.insert(id, self.paint_version);
             }
+            let version = self.repaint_boundaries[&id];
+            let layer = Arc::new(RepaintBoundaryLayer::new(id, version, !has_textures));
+            nodes = vec![LayerNode::new(layer, size, nodes)];
+        }
+
+        if let Some(offset) = offset {
+            nodes = vec![LayerNode::new(Arc::new(OffsetLayer::new(offset)), size, nodes)];
         }
+
+        let state = self.states.get_mut(&id).unwrap();
+        state.set_layer_nodes(nodes.clone(), offset);
+        (nodes, true, has_textures)
+    }
+}
+
+struct LayoutResult {
+    size: Size,
+    offset: Option<Point>,
+    constraints: Option<Constraints>,
+    baseline: Option<f32>,
+}
+
+impl LayoutResult {
+    fn new(
+        size: Size,
+        constraints: impl Into<Option<Constraints>>,
+        baseline: Option<f32>,
+    ) -> Self {
+        LayoutResult {
+            size,
+            offset: None,
+            constraints: constraints.into(),
+            baseline,
+        }
+    }
+}
+
+impl Drop for RenderTree {
+    fn drop(&mut self) {
+        self.remove_widget(self.root_widget);
     }
 }
 
@@ -518,6 +1145,7 @@ pub struct MountContext<'a> {
     texture_ids: &'a mut IndexPool,
     texture: Option<Id>,
     pipeline: &'a mut SharedPipeline,
+    dependencies: HashSet<Id>,
 }
 
 impl<'a> MountContext<'a> {
@@ -533,6 +1161,7 @@ impl<'a> MountContext<'a> {
             tree,
             texture: None,
             pipeline,
+            dependencies: HashSet::new(),
         }
     }
 
@@ -544,64 +1173,77 @@ impl<'a> MountContext<'a> {
         WidgetTexture::new(self.pipeline, id)
     }
 
+    pub fn performance_stats(&self) -> PerformanceStats {
+        self.pipeline.performance_stats()
+    }
+
     pub fn ancestor_widget<T: 'static + Widget>(&self) -> Option<&T> {
-        let mut ids = VecDeque::new();
-        if let Some(parent) = self.tree.parent(self.id) {
-            ids.push_back(parent);
-        }
-        while let Some(id) = ids.pop_front() {
-            if let Some(widget) = self.tree.node(id) {
-                if let Some(widget) = widget.as_any().downcast_ref::<T>() {
-                    return Some(widget);
-                }
-            }
-            if let Some(parent) = self.tree.parent(id) {
-                ids.push_back(parent);
-            }
-        }
-        None
+        find_ancestor_widget(self.tree, self.id).map(|(_, widget)| widget)
+    }
+
+    pub fn inherited_widget<T: 'static + Widget>(&mut self) -> Option<&'a T> {
+        let (id, widget) = find_ancestor_widget(self.tree, self.id)?;
+        self.dependencies.insert(id);
+        Some(widget)
     }
 }
 
 pub struct UpdateContext<'a> {
     id: Id,
     tree: &'a Tree<BoxedWidget>,
+    dependencies: HashSet<Id>,
 }
 
 impl<'a> UpdateContext<'a> {
     pub fn new(id: Id, tree: &'a Tree<BoxedWidget>) -> Self {
-        UpdateContext { id, tree }
+        UpdateContext {
+            id,
+            tree,
+            dependencies: HashSet::new(),
+        }
     }
 
     pub fn ancestor_widget<T: 'static + Widget>(&self) -> Option<&T> {
-        let mut ids = VecDeque::new();
-        if let Some(parent) = self.tree.parent(self.id) {
-            ids.push_back(parent);
-        }
-        while let Some(id) = ids.pop_front() {
-            if let Some(widget) = self.tree.node(id) {
-                if let Some(widget) = widget.as_any().downcast_ref::<T>() {
-                    return Some(widget);
-                }
-            }
-            if let Some(parent) = self.tree.parent(id) {
-                ids.push_back(parent);
+        find_ancestor_widget(self.tree, self.id).map(|(_, widget)| widget)
+    }
+
+    pub fn inherited_widget<T: 'static + Widget>(&mut self) -> Option<&'a T> {
+        let (id, widget) = find_ancestor_widget(self.tree, self.id)?;
+        self.dependencies.insert(id);
+        Some(widget)
+    }
+}
+
+fn find_ancestor_widget<T: 'static + Widget>(tree: &Tree<BoxedWidget>, id: Id) -> Option<(Id, &T)> {
+    let mut ids = VecDeque::new();
+    if let Some(parent) = tree.parent(id) {
+        ids.push_back(parent);
+    }
+    while let Some(id) = ids.pop_front() {
+        if let Some(widget) = tree.node(id) {
+            if let Some(widget) = widget.as_any().downcast_ref::<T>() {
+                return Some((id, widget));
             }
         }
-        None
+        if let Some(parent) = tree.parent(id) {
+            ids.push_back(parent);
+        }
     }
+    None
 }
 
 pub struct BuildContext {
     children: Vec<BoxedWidget>,
     event_emitter: WidgetEventEmitter,
+    event_sender: WidgetEventSender,
 }
 
 impl BuildContext {
-    pub fn new(event_emitter: WidgetEventEmitter) -> Self {
+    pub(crate) fn new(event_emitter: WidgetEventEmitter, event_sender: WidgetEventSender) -> Self {
         BuildContext {
             children: Vec::new(),
             event_emitter,
+            event_sender,
         }
     }
 
@@ -613,4 +1255,12 @@ impl BuildContext {
     pub fn event_emitter(&self) -> WidgetEventEmitter {
         self.event_emitter.clone()
     }
+
+    pub fn event_sender(&self) -> WidgetEventSender {
+        self.event_sender.clone()
+    }
+
+    pub fn handle(&self) -> WidgetHandle {
+        WidgetHandle::new(self.event_sender())
+    }
 }
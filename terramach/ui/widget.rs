@@ -19,9 +19,11 @@
 use std::any::Any;
 use std::cell::{RefCell, RefMut, Ref};
 use std::collections::HashMap;
+use std::future::Future;
 use std::rc::Rc;
+use std::sync::Arc;
 
-use crate::{BuildContext, EventContext, HitTestContext, Id, LayoutContext, MeasuredSize, MountContext, PaintContext, Timer, Timers, UpdateContext, ContainerLayer, WidgetEvents};
+use crate::{BuildContext, EventContext, HitTestContext, Id, LayoutContext, MeasuredSize, MountContext, PaintContext, Timer, Timers, UpdateContext, IntrinsicContext, IntrinsicDimension, ContainerLayer, WidgetEvents, SharedClock, Constraints, SharedLayer, SharedLayerNode, WidgetError, EventWakeup, WidgetEventSender, WidgetHandle, TaskId, Tasks};
 use crate::platform::Cursor;
 
 use terramach_graphics::{Size, Point};
@@ -30,11 +32,51 @@ pub type AnyWidget = dyn Any;
 
 pub type BoxedWidget = Box<dyn Widget>;
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WidgetKey {
+    Index(u64),
+    Name(String),
+}
+
+impl From<u64> for WidgetKey {
+    fn from(index: u64) -> Self {
+        WidgetKey::Index(index)
+    }
+}
+
+impl From<usize> for WidgetKey {
+    fn from(index: usize) -> Self {
+        WidgetKey::Index(index as u64)
+    }
+}
+
+impl From<u32> for WidgetKey {
+    fn from(index: u32) -> Self {
+        WidgetKey::Index(index as u64)
+    }
+}
+
+impl From<String> for WidgetKey {
+    fn from(name: String) -> Self {
+        WidgetKey::Name(name)
+    }
+}
+
+impl From<&str> for WidgetKey {
+    fn from(name: &str) -> Self {
+        WidgetKey::Name(name.to_string())
+    }
+}
+
 pub trait PartialWidget {
     fn as_any(&self) -> &AnyWidget;
 
     fn clone_boxed(&self) -> BoxedWidget;
 
+    fn type_name(&self) -> &'static str {
+        std::any::type_name::<Self>()
+    }
+
     fn same(&self, other: &BoxedWidget) -> bool {
         self.as_any().type_id() == other.as_any().type_id()
     }
@@ -43,10 +85,16 @@ pub trait PartialWidget {
 }
 
 pub trait Widget: PartialWidget {
+    fn key(&self) -> Option<&WidgetKey> {
+        None
+    }
+
     fn mount(&self, _context: &mut WidgetContext, _mount: &mut MountContext) {}
 
     fn update(&self, _context: &mut WidgetContext, _update: &mut UpdateContext) {}
 
+    fn unmount(&self, _context: &mut WidgetContext) {}
+
     fn layout(&self, _context: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let mut size = Size::new_empty();
         let child_constraints = *layout.constraints();
@@ -64,6 +112,39 @@ pub trait Widget: PartialWidget {
         size.constrain(layout.constraints())
     }
 
+    fn min_intrinsic_width(&self, _context: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MinWidth, height)
+    }
+
+    fn max_intrinsic_width(&self, _context: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MaxWidth, height)
+    }
+
+    fn min_intrinsic_height(&self, _context: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MinHeight, width)
+    }
+
+    fn max_intrinsic_height(&self, _context: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MaxHeight, width)
+    }
+
+    /// Widgets laid out with tight constraints are relayout boundaries already. Return `true`
+    /// only if the size of the widget depends on nothing but its constraints, so a change below
+    /// it does not need to lay out its parent. A boundary that ends up with a different size or
+    /// baseline still relays out its parent, and a widget whose intrinsic dimensions an ancestor
+    /// queried is never a boundary.
+    fn relayout_boundary(&self) -> bool {
+        false
+    }
+
+    fn repaint_boundary(&self) -> bool {
+        false
+    }
+
+    fn error_boundary(&self) -> bool {
+        false
+    }
+
     fn build(&self, _context: &mut WidgetContext, _build: &mut BuildContext) {}
 
     fn event(&self, _context: &mut WidgetContext, _event: &mut EventContext) {}
@@ -107,19 +188,39 @@ pub struct WidgetContext {
     pub(crate) timers_ids: HashMap<usize, Id>,
     pub(crate) frame_requested: bool,
     pub(crate) timers: Option<Timers>,
+    pub(crate) tasks: Tasks,
+    pub(crate) error: Option<WidgetError>,
+    clock: SharedClock,
+    event_sender: WidgetEventSender,
 }
 
 impl WidgetContext {
-    pub fn new() -> Self {
+    pub(crate) fn new(clock: SharedClock, event_sender: WidgetEventSender) -> Self {
         WidgetContext {
             state: None,
             frame_requested: false,
             timers: None,
             timers_ids: HashMap::new(),
+            tasks: Tasks::new(),
             cursor: None,
+            error: None,
+            clock,
+            event_sender,
         }
     }
 
+    pub fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
+    pub fn event_sender(&self) -> WidgetEventSender {
+        self.event_sender.clone()
+    }
+
+    pub fn handle(&self) -> WidgetHandle {
+        WidgetHandle::new(self.event_sender())
+    }
+
     pub fn set_state<T: 'static>(&mut self, state: impl Into<Option<T>>) {
         match state.into() {
             None => self.state = None,
@@ -144,7 +245,8 @@ impl WidgetContext {
     }
 
     pub fn schedule_timer(&mut self, id: usize, timer: Timer) {
-        let timers = self.timers.get_or_insert_with(|| Timers::new());
+        let clock = &self.clock;
+        let timers = self.timers.get_or_insert_with(|| Timers::new(clock.clone()));
         let timer_id = timers.add(timer);
         let previous_timer = self.timers_ids.insert(id, timer_id);
         if let Some(previous_timer) = previous_timer {
@@ -175,6 +277,23 @@ impl WidgetContext {
         }
     }
 
+    pub fn spawn<T: Send + 'static>(&mut self, future: impl Future<Output = T> + 'static) -> TaskId {
+        let event_sender = self.event_sender();
+        self.tasks.spawn(future, event_sender)
+    }
+
+    pub fn cancel_task(&mut self, id: TaskId) -> bool {
+        self.tasks.cancel(id)
+    }
+
+    pub fn cancel_all_tasks(&mut self) {
+        self.tasks.clear();
+    }
+
+    pub fn has_active_tasks(&self) -> bool {
+        !self.tasks.is_empty()
+    }
+
     pub fn set_cursor(&mut self, cursor: impl Into<Option<Cursor>>) {
         self.cursor = cursor.into();
     }
@@ -182,36 +301,54 @@ impl WidgetContext {
     pub fn cursor(&self) -> Option<Cursor> {
         self.cursor
     }
+
+    pub fn error(&self) -> Option<&WidgetError> {
+        self.error.as_ref()
+    }
 }
 
 pub(crate) struct WidgetState {
     context: RefCell<WidgetContext>,
     offset: Option<Point>,
     size: Option<Size>,
+    constraints: Option<Constraints>,
+    baseline: Option<f32>,
+    overflow: Size,
+    relayout_boundary: bool,
+    paint_count: u64,
     mounted: bool,
     need_paint: bool,
     need_layout: bool,
     need_build: bool,
-    layer: Option<ContainerLayer>,
-    leaf_layer: Option<ContainerLayer>,
+    layer: Option<SharedLayer>,
+    leaf_layer: Option<SharedLayer>,
+    layer_nodes: Option<(Vec<SharedLayerNode>, Option<Point>)>,
     events: WidgetEvents,
     texture: Option<Id>,
 }
 
 impl WidgetState {
-    pub fn new() -> WidgetState {
+    pub fn new(id: Id, clock: SharedClock, event_wakeup: EventWakeup) -> WidgetState {
+        let events = WidgetEvents::new();
+        let event_sender = WidgetEventSender::new(id, &events, event_wakeup);
         WidgetState {
-            context: RefCell::new(WidgetContext::new()),
+            context: RefCell::new(WidgetContext::new(clock, event_sender)),
             offset: None,
             size: None,
+            constraints: None,
+            baseline: None,
+            overflow: Size::default(),
+            relayout_boundary: false,
+            paint_count: 0,
             mounted: false,
             need_layout: true,
             need_paint: true,
             need_build: true,
             layer: None,
             leaf_layer: None,
+            layer_nodes: None,
             texture: None,
-            events: WidgetEvents::new(),
+            events,
         }
     }
 
@@ -231,20 +368,31 @@ impl WidgetState {
         &mut self.events
     }
 
-    pub fn layer(&self) -> Option<&ContainerLayer> {
+    pub fn layer(&self) -> Option<&SharedLayer> {
         self.layer.as_ref()
     }
 
-    pub fn leaf_layer(&self) -> Option<&ContainerLayer> {
+    pub fn leaf_layer(&self) -> Option<&SharedLayer> {
         self.leaf_layer.as_ref()
     }
 
     pub fn set_layer(&mut self, layer: impl Into<Option<ContainerLayer>>) {
-        self.layer = layer.into();
+        self.layer = layer.into().map(|layer| Arc::new(layer) as SharedLayer);
     }
 
     pub fn set_leaf_layer(&mut self, layer: impl Into<Option<ContainerLayer>>) {
-        self.leaf_layer = layer.into();
+        self.leaf_layer = layer.into().map(|layer| Arc::new(layer) as SharedLayer);
+    }
+
+    pub fn layer_nodes(&self, offset: Option<Point>) -> Option<&Vec<SharedLayerNode>> {
+        match &self.layer_nodes {
+            Some((nodes, nodes_offset)) if *nodes_offset == offset => Some(nodes),
+            _ => None,
+        }
+    }
+
+    pub fn set_layer_nodes(&mut self, nodes: Vec<SharedLayerNode>, offset: Option<Point>) {
+        self.layer_nodes = Some((nodes, offset));
     }
 
     pub fn mounted(&self) -> bool {
@@ -288,6 +436,46 @@ impl WidgetState {
         self.offset = offset.into();
     }
 
+    pub fn constraints(&self) -> Option<Constraints> {
+        self.constraints
+    }
+
+    pub fn set_constraints(&mut self, constraints: impl Into<Option<Constraints>>) {
+        self.constraints = constraints.into();
+    }
+
+    pub fn baseline(&self) -> Option<f32> {
+        self.baseline
+    }
+
+    pub fn set_baseline(&mut self, baseline: impl Into<Option<f32>>) {
+        self.baseline = baseline.into();
+    }
+
+    pub fn overflow(&self) -> Size {
+        self.overflow
+    }
+
+    pub fn set_overflow(&mut self, overflow: Size) {
+        self.overflow = overflow;
+    }
+
+    pub fn relayout_boundary(&self) -> bool {
+        self.relayout_boundary
+    }
+
+    pub fn set_relayout_boundary(&mut self, relayout_boundary: bool) {
+        self.relayout_boundary = relayout_boundary;
+    }
+
+    pub fn paint_count(&self) -> u64 {
+        self.paint_count
+    }
+
+    pub fn set_paint_count(&mut self, paint_count: u64) {
+        self.paint_count = paint_count;
+    }
+
     pub fn context(&self) -> Ref<WidgetContext> {
         self.context.borrow()
     }
@@ -20,7 +20,7 @@ use std::hash::{Hash, Hasher};
 
 use crate::{
     AnyWidget, BoxedWidget, BuildContext, Constraints, LayoutContext, MeasuredSize, PaintContext,
-    PartialWidget, Widget, WidgetContext,
+    PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
 use terramach_graphics::{Point, Size};
@@ -44,6 +44,7 @@ pub enum CrossAxisAlignment {
     Middle,
     End,
     Stretch,
+    Baseline,
 }
 
 impl Default for CrossAxisAlignment {
@@ -139,7 +140,7 @@ impl From<(f32, f32)> for Alignment {
     }
 }
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Align {
     alignment: Alignment,
     child: BoxedWidget,
@@ -147,10 +148,7 @@ pub struct Align {
 
 impl Align {
     pub fn new(alignment: impl Into<Option<Alignment>>, child: impl Into<BoxedWidget>) -> Self {
-        Align {
-            alignment: alignment.into().unwrap_or_default(),
-            child: child.into(),
-        }
+        Align::builder(child).with_alignment(alignment.into().unwrap_or_default())
     }
 }
 
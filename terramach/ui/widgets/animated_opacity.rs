@@ -66,9 +66,10 @@ impl Widget for AnimatedOpacity {
     fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
         match event.get() {
             Event::Frame(_) => {
+                let clock = context.clock();
                 let state = context.state_mut::<AnimatedOpacityState>().unwrap();
                 if let Some(animation) = &mut state.animation {
-                    if animation.advance() {
+                    if animation.advance(&clock) {
                         context.request_frame();
                     } else {
                         state.animation = None;
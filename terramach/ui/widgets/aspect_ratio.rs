@@ -17,31 +17,26 @@
  */
 
 use crate::{
-    AnyWidget, BoxedWidget, BuildContext, Constraints, LayoutContext, MeasuredSize, PaintContext,
-    PartialWidget, Widget, WidgetContext,
+    AnyWidget, BoxedWidget, BuildContext, Constraints, IntrinsicContext, LayoutContext,
+    MeasuredSize, PaintContext, PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
 use terramach_graphics::Size;
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct AspectRatio {
+    #[builder(required)]
     ratio: f32,
     child: Option<BoxedWidget>,
 }
 
 impl AspectRatio {
     pub fn new(ratio: impl Into<f32>, child: impl Into<BoxedWidget>) -> Self {
-        AspectRatio {
-            ratio: ratio.into(),
-            child: Some(child.into()),
-        }
+        AspectRatio::builder(ratio.into()).with_child(child)
     }
 
     pub fn new_empty(ratio: impl Into<f32>) -> Self {
-        AspectRatio {
-            ratio: ratio.into(),
-            child: None,
-        }
+        AspectRatio::builder(ratio.into())
     }
 
     fn apply_ratio(&self, size: Size) -> Size {
@@ -54,6 +49,38 @@ impl AspectRatio {
 }
 
 impl Widget for AspectRatio {
+    fn min_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        if height.is_finite() {
+            height * self.ratio
+        } else {
+            intrinsic.min_intrinsic_width(0, height)
+        }
+    }
+
+    fn max_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        if height.is_finite() {
+            height * self.ratio
+        } else {
+            intrinsic.max_intrinsic_width(0, height)
+        }
+    }
+
+    fn min_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        if width.is_finite() {
+            width / self.ratio
+        } else {
+            intrinsic.min_intrinsic_height(0, width)
+        }
+    }
+
+    fn max_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        if width.is_finite() {
+            width / self.ratio
+        } else {
+            intrinsic.max_intrinsic_height(0, width)
+        }
+    }
+
     fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let child_constraints = Constraints::new(
             self.apply_ratio(layout.constraints().minimum_size()),
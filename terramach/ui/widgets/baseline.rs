@@ -0,0 +1,82 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use crate::{
+    AnyWidget, BoxedWidget, BuildContext, Constraints, IntrinsicContext, LayoutContext, MeasuredSize, MountContext,
+    PartialWidget, WidgetBuilder, Widget, WidgetContext,
+};
+
+use terramach_graphics::Size;
+
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
+pub struct Baseline {
+    #[builder(required)]
+    baseline: f32,
+    child: BoxedWidget,
+}
+
+impl Baseline {
+    pub fn new(baseline: impl Into<f32>, child: impl Into<BoxedWidget>) -> Self {
+        Baseline::builder(baseline.into(), child)
+    }
+}
+
+#[derive(Default)]
+struct BaselineState {
+    top: f32,
+}
+
+impl Baseline {
+    fn top(&self, context: &WidgetContext) -> f32 {
+        context.state::<BaselineState>().map_or(0.0, |state| state.top)
+    }
+}
+
+impl Widget for Baseline {
+    fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
+        context.set_state(BaselineState::default());
+    }
+
+    fn min_intrinsic_height(&self, context: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        (self.top(context) + intrinsic.min_intrinsic_height(0, width)).max(0.0)
+    }
+
+    fn max_intrinsic_height(&self, context: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        (self.top(context) + intrinsic.max_intrinsic_height(0, width)).max(0.0)
+    }
+
+    fn layout(&self, context: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
+        let child_constraints = Constraints::new_loose(layout.constraints().maximum_size());
+        let child_size = layout
+            .layout_child(0, &child_constraints)
+            .unwrap_or_default();
+        let top = layout
+            .child_baseline(0)
+            .map_or(0.0, |child_baseline| self.baseline - child_baseline);
+        if let Some(state) = context.state_mut::<BaselineState>() {
+            state.top = top;
+        }
+        layout.set_child_offset(0, (0.0, top));
+        layout.set_baseline(self.baseline);
+        Size::new(child_size.width, top + child_size.height).constrain(layout.constraints())
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(self.child.clone());
+    }
+}
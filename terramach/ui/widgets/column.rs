@@ -19,8 +19,8 @@
 use terramach_graphics::Size;
 
 use crate::{
-    AnyWidget, BoxedWidget, BuildContext, Constraints, HitTestContext, LayoutContext, MeasuredSize,
-    PartialWidget, Widget, WidgetContext,
+    AnyWidget, BoxedWidget, BuildContext, Constraints, HitTestContext, IntrinsicContext,
+    IntrinsicDimension, LayoutContext, MeasuredSize, PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 use crate::widgets::{MainAxisAlignment, CrossAxisAlignment};
 
@@ -47,8 +47,9 @@ impl Flex {
     }
 }
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Column {
+    #[builder(skip)]
     children: Vec<Flex>,
     horizontal_alignment: Option<CrossAxisAlignment>,
     vertical_alignment: Option<MainAxisAlignment>,
@@ -65,11 +66,9 @@ impl Column {
         horizontal_alignment: impl Into<Option<CrossAxisAlignment>>,
         vertical_alignment: impl Into<Option<MainAxisAlignment>>,
     ) -> Self {
-        Column {
-            children: Vec::new(),
-            horizontal_alignment: horizontal_alignment.into(),
-            vertical_alignment: vertical_alignment.into(),
-        }
+        Column::builder()
+            .with_horizontal_alignment(horizontal_alignment)
+            .with_vertical_alignment(vertical_alignment)
     }
 
     pub fn with_child(self, widget: impl Into<BoxedWidget>) -> Self {
@@ -87,6 +86,22 @@ impl Column {
 }
 
 impl Widget for Column {
+    fn min_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, _: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MinWidth, f32::INFINITY)
+    }
+
+    fn max_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, _: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MaxWidth, f32::INFINITY)
+    }
+
+    fn min_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        intrinsic.sum_of_children(IntrinsicDimension::MinHeight, width)
+    }
+
+    fn max_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        intrinsic.sum_of_children(IntrinsicDimension::MaxHeight, width)
+    }
+
     fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let minimum_size = layout.constraints().minimum_size();
         let maximum_size = layout.constraints().maximum_size();
@@ -159,7 +174,9 @@ impl Widget for Column {
             let child_size = &sizes[child];
             let child_offset_x = if let Some(alignment) = &self.horizontal_alignment {
                 match alignment {
-                    CrossAxisAlignment::Start | CrossAxisAlignment::Stretch => 0.0,
+                    CrossAxisAlignment::Start
+                    | CrossAxisAlignment::Stretch
+                    | CrossAxisAlignment::Baseline => 0.0,
                     CrossAxisAlignment::Middle => (size.width - content_size.width) / 2.0,
                     CrossAxisAlignment::End => size.width - child_size.width,
                 }
@@ -17,35 +17,58 @@
  */
 
 use crate::{
-    AnyWidget, BoxedWidget, BuildContext, Constraints, LayoutContext, MeasuredSize, PaintContext,
-    PartialWidget, Widget, WidgetContext,
+    AnyWidget, BoxedWidget, BuildContext, Constraints, IntrinsicContext, LayoutContext,
+    MeasuredSize, PaintContext, PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
 use terramach_graphics::Size;
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Constrained {
+    #[builder(required)]
     constraints: Constraints,
     child: Option<BoxedWidget>,
 }
 
 impl Constrained {
     pub fn new_empty(constraints: impl Into<Constraints>) -> Self {
-        Constrained {
-            constraints: constraints.into(),
-            child: None,
-        }
+        Constrained::builder(constraints)
     }
 
     pub fn new(constraints: impl Into<Constraints>, child: impl Into<BoxedWidget>) -> Self {
-        Constrained {
-            constraints: constraints.into(),
-            child: Some(child.into()),
-        }
+        Constrained::builder(constraints).with_child(child)
+    }
+
+    fn constrain_width(&self, width: f32) -> f32 {
+        width
+            .min(self.constraints.maximum_size().width)
+            .max(self.constraints.minimum_size().width)
+    }
+
+    fn constrain_height(&self, height: f32) -> f32 {
+        height
+            .min(self.constraints.maximum_size().height)
+            .max(self.constraints.minimum_size().height)
     }
 }
 
 impl Widget for Constrained {
+    fn min_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        self.constrain_width(intrinsic.min_intrinsic_width(0, height))
+    }
+
+    fn max_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        self.constrain_width(intrinsic.max_intrinsic_width(0, height))
+    }
+
+    fn min_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        self.constrain_height(intrinsic.min_intrinsic_height(0, width))
+    }
+
+    fn max_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        self.constrain_height(intrinsic.max_intrinsic_height(0, width))
+    }
+
     fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let child_constraints = self.constraints.constrain(layout.constraints());
         layout
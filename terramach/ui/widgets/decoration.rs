@@ -20,7 +20,7 @@ use std::hash::{Hash, Hasher};
 
 use crate::{
     BoxedWidget, BuildContext, Constraints, LayoutContext, MeasuredSize, PaintContext,
-    PartialWidget, Widget, WidgetContext,
+    PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
 use terramach_graphics::{Color, Color4f, Paint, Point, RRect, Rect, Size};
@@ -105,7 +105,7 @@ impl Hash for BorderRadius {
     }
 }
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Decoration {
     background_color: Option<Color>,
     border_radius: Option<BorderRadius>,
@@ -117,11 +117,9 @@ impl Decoration {
         background_color: impl Into<Option<Color>>,
         border_radius: impl Into<Option<BorderRadius>>,
     ) -> Self {
-        Decoration {
-            background_color: background_color.into(),
-            border_radius: border_radius.into(),
-            child: None,
-        }
+        Decoration::builder()
+            .with_background_color(background_color)
+            .with_border_radius(border_radius)
     }
 
     pub fn new(
@@ -129,11 +127,7 @@ impl Decoration {
         border_radius: impl Into<Option<BorderRadius>>,
         child: impl Into<BoxedWidget>,
     ) -> Self {
-        Decoration {
-            background_color: background_color.into(),
-            border_radius: border_radius.into(),
-            child: Some(child.into()),
-        }
+        Decoration::new_empty(background_color, border_radius).with_child(child)
     }
 }
 
@@ -0,0 +1,82 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use std::rc::Rc;
+
+use crate::{
+    AnyWidget, BoxedWidget, BuildContext, PartialWidget, WidgetBuilder, Widget, WidgetContext, WidgetError,
+};
+use crate::widgets::{Text, TextStyle};
+
+use terramach_graphics::Color;
+
+pub type ErrorFallback = Rc<dyn Fn(&WidgetError) -> BoxedWidget>;
+
+#[derive(Clone, PartialWidget, WidgetBuilder)]
+pub struct ErrorBoundary {
+    #[builder(skip)]
+    fallback: Option<ErrorFallback>,
+    child: BoxedWidget,
+}
+
+impl ErrorBoundary {
+    pub fn new(child: impl Into<BoxedWidget>) -> Self {
+        ErrorBoundary::builder(child)
+    }
+
+    pub fn with_fallback(mut self, fallback: impl Fn(&WidgetError) -> BoxedWidget + 'static) -> Self {
+        self.fallback = Some(Rc::new(fallback));
+        self
+    }
+
+    fn default_fallback(error: &WidgetError) -> BoxedWidget {
+        Text::new(
+            error.to_string(),
+            TextStyle::default().with_color(Color::RED),
+        ).into()
+    }
+}
+
+impl PartialEq for ErrorBoundary {
+    fn eq(&self, other: &Self) -> bool {
+        let same_fallback = match (&self.fallback, &other.fallback) {
+            (Some(fallback), Some(other_fallback)) => Rc::ptr_eq(fallback, other_fallback),
+            (None, None) => true,
+            _ => false,
+        };
+        same_fallback && self.child == other.child
+    }
+}
+
+impl Widget for ErrorBoundary {
+    fn error_boundary(&self) -> bool {
+        true
+    }
+
+    fn build(&self, context: &mut WidgetContext, build: &mut BuildContext) {
+        if let Some(error) = context.error() {
+            let fallback = match &self.fallback {
+                Some(fallback) => fallback(error),
+                None => ErrorBoundary::default_fallback(error),
+            };
+            build.add_child(fallback);
+        } else {
+            build.add_child(self.child.clone());
+        }
+    }
+}
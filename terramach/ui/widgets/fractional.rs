@@ -17,35 +17,54 @@
  */
 
 use crate::{
-    AnyWidget, BoxedWidget, BuildContext, Constraints, LayoutContext, MeasuredSize, PaintContext,
-    PartialWidget, Widget, WidgetContext,
+    AnyWidget, BoxedWidget, BuildContext, Constraints, IntrinsicContext, LayoutContext,
+    MeasuredSize, PaintContext, PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
 use terramach_graphics::Size;
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Fractional {
+    #[builder(required)]
     fraction: Size,
     child: Option<BoxedWidget>,
 }
 
 impl Fractional {
     pub fn new_empty(fraction: impl Into<Size>) -> Self {
-        Fractional {
-            fraction: fraction.into(),
-            child: None,
-        }
+        Fractional::builder(fraction)
     }
 
     pub fn new(fraction: impl Into<Size>, child: impl Into<BoxedWidget>) -> Self {
-        Fractional {
-            fraction: fraction.into(),
-            child: Some(child.into()),
+        Fractional::builder(fraction).with_child(child)
+    }
+
+    fn unapply_fraction(&self, extent: f32, fraction: f32) -> f32 {
+        if fraction > 0.0 {
+            extent / fraction
+        } else {
+            0.0
         }
     }
 }
 
 impl Widget for Fractional {
+    fn min_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        self.unapply_fraction(intrinsic.min_intrinsic_width(0, height * self.fraction.height), self.fraction.width)
+    }
+
+    fn max_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        self.unapply_fraction(intrinsic.max_intrinsic_width(0, height * self.fraction.height), self.fraction.width)
+    }
+
+    fn min_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        self.unapply_fraction(intrinsic.min_intrinsic_height(0, width * self.fraction.width), self.fraction.height)
+    }
+
+    fn max_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        self.unapply_fraction(intrinsic.max_intrinsic_height(0, width * self.fraction.width), self.fraction.height)
+    }
+
     fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let maximum_size = layout.constraints().maximum_size();
         let child_maximum_size = Size::new(
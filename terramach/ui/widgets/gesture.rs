@@ -16,22 +16,56 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
 use crate::{
-    AnyWidget, BoxedWidget, BuildContext, Event, EventContext, EventId, HitTestContext,
-    LayoutContext, MountContext, PanGesture, PartialWidget, TapGesture, TapGestureState, Touches,
-    Widget, WidgetContext, WidgetEventEmitter,
+    AnyWidget, BoxedWidget, BuildContext, Clock, DoubleTapGesture, Event, EventContext, EventId, HitTestContext,
+    LayoutContext, LongPressGesture, LongPressGestureState, MountContext, PanGesture, PanGestureState,
+    PartialWidget, TapGesture, TapGestureState, Timer, Touches, UpdateContext, Widget, WidgetContext,
+    WidgetEventEmitter,
 };
 
+use terramach_graphics::Point;
+
+type GestureCallback = Rc<dyn Fn()>;
+type PanCallback = Rc<dyn Fn(Point)>;
+
+#[derive(Clone, Default)]
+struct GestureCallbacks {
+    tap: Option<GestureCallback>,
+    double_tap: Option<GestureCallback>,
+    long_press: Option<GestureCallback>,
+    pan_start: Option<PanCallback>,
+    pan_update: Option<PanCallback>,
+    pan_end: Option<GestureCallback>,
+}
+
+impl PartialEq for GestureCallbacks {
+    fn eq(&self, other: &Self) -> bool {
+        same_callback(&self.tap, &other.tap)
+            && same_callback(&self.double_tap, &other.double_tap)
+            && same_callback(&self.long_press, &other.long_press)
+            && same_callback(&self.pan_start, &other.pan_start)
+            && same_callback(&self.pan_update, &other.pan_update)
+            && same_callback(&self.pan_end, &other.pan_end)
+    }
+}
+
 #[derive(Clone, PartialWidget)]
 pub struct Gesture {
     tap: Option<TapGesture>,
+    double_tap: Option<DoubleTapGesture>,
+    long_press: Option<LongPressGesture>,
     pan: Option<PanGesture>,
-    event_id: EventId,
-    event_emitter: WidgetEventEmitter,
+    event: Option<(EventId, WidgetEventEmitter)>,
+    callbacks: GestureCallbacks,
     child: BoxedWidget,
 }
 
 impl Gesture {
+    const LONG_PRESS: usize = 1;
+
     pub fn new(
         event_id: impl Into<EventId>,
         event_emitter: WidgetEventEmitter,
@@ -40,32 +74,115 @@ impl Gesture {
         child: impl Into<BoxedWidget>,
     ) -> Self {
         Gesture {
-            event_id: event_id.into(),
-            event_emitter,
             tap: tap.into(),
+            double_tap: None,
+            long_press: None,
             pan: pan.into(),
+            event: Some((event_id.into(), event_emitter)),
+            callbacks: GestureCallbacks::default(),
             child: child.into(),
         }
     }
+
+    pub fn new_child(child: impl Into<BoxedWidget>) -> Self {
+        Gesture {
+            tap: None,
+            double_tap: None,
+            long_press: None,
+            pan: None,
+            event: None,
+            callbacks: GestureCallbacks::default(),
+            child: child.into(),
+        }
+    }
+
+    pub fn on_tap(mut self, callback: impl Fn() + 'static) -> Self {
+        self.tap.get_or_insert_with(TapGesture::default);
+        self.callbacks.tap = Some(Rc::new(callback));
+        self
+    }
+
+    pub fn on_double_tap(mut self, callback: impl Fn() + 'static) -> Self {
+        self.double_tap.get_or_insert_with(DoubleTapGesture::default);
+        self.callbacks.double_tap = Some(Rc::new(callback));
+        self
+    }
+
+    pub fn on_long_press(mut self, callback: impl Fn() + 'static) -> Self {
+        self.long_press.get_or_insert_with(LongPressGesture::default);
+        self.callbacks.long_press = Some(Rc::new(callback));
+        self
+    }
+
+    pub fn on_pan_start(mut self, callback: impl Fn(Point) + 'static) -> Self {
+        self.pan.get_or_insert_with(PanGesture::default);
+        self.callbacks.pan_start = Some(Rc::new(callback));
+        self
+    }
+
+    pub fn on_pan_update(mut self, callback: impl Fn(Point) + 'static) -> Self {
+        self.pan.get_or_insert_with(PanGesture::default);
+        self.callbacks.pan_update = Some(Rc::new(callback));
+        self
+    }
+
+    pub fn on_pan_end(mut self, callback: impl Fn() + 'static) -> Self {
+        self.pan.get_or_insert_with(PanGesture::default);
+        self.callbacks.pan_end = Some(Rc::new(callback));
+        self
+    }
+
+    fn dispatch(&self, context: &mut WidgetContext, gestures: Vec<RecognizedGesture>) -> bool {
+        let mut handled = false;
+        for gesture in gestures {
+            handled |= match gesture {
+                RecognizedGesture::Tap => {
+                    let mut handled = invoke(&self.callbacks.tap);
+                    if let Some((event_id, event_emitter)) = &self.event {
+                        event_emitter.clone().emit_event(Event::Tap(*event_id));
+                        handled = true;
+                    }
+                    handled
+                }
+                RecognizedGesture::DoubleTap => invoke(&self.callbacks.double_tap),
+                RecognizedGesture::LongPressBegan(duration) => {
+                    context.schedule_timer(Gesture::LONG_PRESS, Timer::new(duration, None));
+                    false
+                }
+                RecognizedGesture::LongPressCancelled => {
+                    context.cancel_timer(Gesture::LONG_PRESS);
+                    false
+                }
+                RecognizedGesture::LongPress => invoke(&self.callbacks.long_press),
+                RecognizedGesture::PanStart(location) => invoke_pan(&self.callbacks.pan_start, location),
+                RecognizedGesture::PanUpdate(location) => invoke_pan(&self.callbacks.pan_update, location),
+                RecognizedGesture::PanEnd => invoke(&self.callbacks.pan_end),
+            };
+        }
+        handled
+    }
 }
 
 impl PartialEq for Gesture {
     fn eq(&self, other: &Self) -> bool {
         self.tap.is_some() == other.tap.is_some()
+            && self.double_tap.is_some() == other.double_tap.is_some()
+            && self.long_press.is_some() == other.long_press.is_some()
             && self.pan.is_some() == other.pan.is_some()
-            && self.event_id == other.event_id
+            && self.event.as_ref().map(|(event_id, _)| event_id) == other.event.as_ref().map(|(event_id, _)| event_id)
+            && self.callbacks == other.callbacks
             && self.child == other.child
     }
 }
 
 impl Widget for Gesture {
     fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
-        context.set_state(GestureState::new(
-            self.event_id,
-            self.event_emitter.clone(),
-            self.tap.clone(),
-            self.pan.clone(),
-        ));
+        context.set_state(GestureState::new(self));
+    }
+
+    fn update(&self, context: &mut WidgetContext, _: &mut UpdateContext) {
+        let state = context.state_mut::<GestureState>().unwrap();
+        state.update(self);
     }
 
     fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
@@ -73,22 +190,31 @@ impl Widget for Gesture {
     }
 
     fn event(&self, context: &mut WidgetContext, event: &mut EventContext) {
-        match event.get() {
+        let gestures = match event.get() {
             Event::Touch(touches) => {
+                let now = context.clock().now();
                 let state = context.state_mut::<GestureState>().unwrap();
-                if state.recognize(touches) {
-                    event.mark_need_event();
-                }
+                state.recognize(touches, now)
+            }
+            Event::Timer(timer) if *timer == Gesture::LONG_PRESS => {
+                let now = context.clock().now();
+                let state = context.state_mut::<GestureState>().unwrap();
+                state.recognize_long_press(now)
             }
             Event::Enter => {
                 let state = context.state_mut::<GestureState>().unwrap();
                 state.hover = true;
+                return;
             }
             Event::Leave => {
                 let state = context.state_mut::<GestureState>().unwrap();
                 state.hover = false;
+                return;
             }
-            _ => {}
+            _ => return,
+        };
+        if self.dispatch(context, gestures) {
+            event.mark_need_event();
         }
     }
 
@@ -97,37 +223,128 @@ impl Widget for Gesture {
     }
 }
 
+enum RecognizedGesture {
+    Tap,
+    DoubleTap,
+    LongPressBegan(Duration),
+    LongPressCancelled,
+    LongPress,
+    PanStart(Point),
+    PanUpdate(Point),
+    PanEnd,
+}
+
 struct GestureState {
-    event_id: EventId,
-    event_emitter: WidgetEventEmitter,
     tap: Option<TapGesture>,
+    double_tap: Option<DoubleTapGesture>,
+    long_press: Option<LongPressGesture>,
     pan: Option<PanGesture>,
     hover: bool,
 }
 
 impl GestureState {
-    pub fn new(
-        event_id: EventId,
-        event_emitter: WidgetEventEmitter,
-        tap: Option<TapGesture>,
-        pan: Option<PanGesture>,
-    ) -> Self {
+    pub fn new(gesture: &Gesture) -> Self {
         GestureState {
-            event_id,
-            event_emitter,
-            tap,
-            pan,
+            tap: gesture.tap.clone(),
+            double_tap: gesture.double_tap.clone(),
+            long_press: gesture.long_press.clone(),
+            pan: gesture.pan.clone(),
             hover: false,
         }
     }
 
-    pub fn recognize(&mut self, touches: &Touches) -> bool {
+    pub fn update(&mut self, gesture: &Gesture) {
+        update_recognizer(&mut self.tap, &gesture.tap);
+        update_recognizer(&mut self.double_tap, &gesture.double_tap);
+        update_recognizer(&mut self.long_press, &gesture.long_press);
+        update_recognizer(&mut self.pan, &gesture.pan);
+    }
+
+    pub fn recognize(&mut self, touches: &Touches, now: Instant) -> Vec<RecognizedGesture> {
+        let mut gestures = Vec::new();
         if let Some(tap) = &mut self.tap {
             if tap.update(touches) == TapGestureState::Ended && self.hover {
-                self.event_emitter.emit_event(Event::Tap(self.event_id));
-                return true;
+                gestures.push(RecognizedGesture::Tap);
+            }
+        }
+        if let Some(double_tap) = &mut self.double_tap {
+            if double_tap.update(touches, now) == TapGestureState::Ended && self.hover {
+                gestures.push(RecognizedGesture::DoubleTap);
+            }
+        }
+        if let Some(long_press) = &mut self.long_press {
+            let active = long_press.is_active();
+            match long_press.update(touches, now) {
+                LongPressGestureState::Began if !active => {
+                    gestures.push(RecognizedGesture::LongPressBegan(long_press.duration()));
+                }
+                LongPressGestureState::Recognized => {
+                    gestures.push(RecognizedGesture::LongPressCancelled);
+                    gestures.push(RecognizedGesture::LongPress);
+                }
+                LongPressGestureState::Possible | LongPressGestureState::Ended if active => {
+                    gestures.push(RecognizedGesture::LongPressCancelled);
+                }
+                _ => {}
+            }
+        }
+        if let Some(pan) = &mut self.pan {
+            match pan.update(touches) {
+                PanGestureState::Began(location) => gestures.push(RecognizedGesture::PanStart(location)),
+                PanGestureState::Changed(location) => gestures.push(RecognizedGesture::PanUpdate(location)),
+                PanGestureState::Ended => gestures.push(RecognizedGesture::PanEnd),
+                PanGestureState::Possible => {}
+            }
+        }
+        gestures
+    }
+
+    pub fn recognize_long_press(&mut self, now: Instant) -> Vec<RecognizedGesture> {
+        match &mut self.long_press {
+            Some(long_press) if long_press.is_active() => match long_press.fire(now) {
+                LongPressGestureState::Recognized => vec![RecognizedGesture::LongPress],
+                _ => Vec::new(),
+            },
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn update_recognizer<T: Clone>(state: &mut Option<T>, recognizer: &Option<T>) {
+    match recognizer {
+        Some(recognizer) => {
+            if state.is_none() {
+                *state = Some(recognizer.clone());
             }
         }
-        false
+        None => *state = None,
+    }
+}
+
+fn same_callback<T: ?Sized>(one: &Option<Rc<T>>, other: &Option<Rc<T>>) -> bool {
+    match (one, other) {
+        (Some(one), Some(other)) => Rc::ptr_eq(one, other),
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+fn invoke(callback: &Option<GestureCallback>) -> bool {
+    match callback {
+        Some(callback) => {
+            callback();
+            true
+        }
+        None => false,
+    }
+}
+
+fn invoke_pan(callback: &Option<PanCallback>, location: Point) -> bool {
+    match callback {
+        Some(callback) => {
+            callback(location);
+            true
+        }
+        None => false,
     }
 }
@@ -18,8 +18,8 @@
 
 use crate::widgets::Alignment;
 use crate::{
-    BoxedWidget, Fit, HitTestContext, LayoutContext, MeasuredSize, PaintContext, PartialWidget,
-    Widget, WidgetContext,
+    BoxedWidget, Fit, HitTestContext, IntrinsicContext, LayoutContext, MeasuredSize, PaintContext,
+    PartialWidget, Widget, WidgetContext,
 };
 
 use terramach_graphics::{
@@ -73,6 +73,22 @@ impl PartialEq for Image {
 }
 
 impl Widget for Image {
+    fn min_intrinsic_width(&self, context: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        self.max_intrinsic_width(context, intrinsic, height)
+    }
+
+    fn max_intrinsic_width(&self, _: &WidgetContext, _: &IntrinsicContext, height: f32) -> f32 {
+        Size::from_isize(self.image.dimensions()).fit(Size::new_unbound_width(height), Fit::Contain).width
+    }
+
+    fn min_intrinsic_height(&self, context: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        self.max_intrinsic_height(context, intrinsic, width)
+    }
+
+    fn max_intrinsic_height(&self, _: &WidgetContext, _: &IntrinsicContext, width: f32) -> f32 {
+        Size::from_isize(self.image.dimensions()).fit(Size::new_unbound_height(width), Fit::Contain).height
+    }
+
     fn layout(&self, _context: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         layout.constraints().maximum_size()
     }
@@ -0,0 +1,98 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use crate::{
+    AnyWidget, BoxedWidget, BuildContext, Constraints, IntrinsicContext, LayoutContext,
+    MeasuredSize, PartialWidget, WidgetBuilder, Widget, WidgetContext,
+};
+
+use terramach_graphics::Size;
+
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
+pub struct IntrinsicWidth {
+    child: BoxedWidget,
+}
+
+impl IntrinsicWidth {
+    pub fn new(child: impl Into<BoxedWidget>) -> Self {
+        IntrinsicWidth::builder(child)
+    }
+}
+
+impl Widget for IntrinsicWidth {
+    fn min_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        intrinsic.max_intrinsic_width(0, height)
+    }
+
+    fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
+        let constraints = *layout.constraints();
+        let width = layout
+            .max_intrinsic_width(0, constraints.maximum_size().height)
+            .min(constraints.maximum_size().width)
+            .max(constraints.minimum_size().width);
+        let child_constraints = Constraints::new(
+            Size::new(width, constraints.minimum_size().height),
+            Size::new(width, constraints.maximum_size().height),
+        );
+        layout
+            .layout_child(0, &child_constraints)
+            .unwrap_or(child_constraints.minimum_size())
+            .constrain(&child_constraints)
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(self.child.clone());
+    }
+}
+
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
+pub struct IntrinsicHeight {
+    child: BoxedWidget,
+}
+
+impl IntrinsicHeight {
+    pub fn new(child: impl Into<BoxedWidget>) -> Self {
+        IntrinsicHeight::builder(child)
+    }
+}
+
+impl Widget for IntrinsicHeight {
+    fn min_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        intrinsic.max_intrinsic_height(0, width)
+    }
+
+    fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
+        let constraints = *layout.constraints();
+        let height = layout
+            .max_intrinsic_height(0, constraints.maximum_size().width)
+            .min(constraints.maximum_size().height)
+            .max(constraints.minimum_size().height);
+        let child_constraints = Constraints::new(
+            Size::new(constraints.minimum_size().width, height),
+            Size::new(constraints.maximum_size().width, height),
+        );
+        layout
+            .layout_child(0, &child_constraints)
+            .unwrap_or(child_constraints.minimum_size())
+            .constrain(&child_constraints)
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(self.child.clone());
+    }
+}
@@ -0,0 +1,42 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use crate::{AnyWidget, BoxedWidget, BuildContext, PartialWidget, Widget, WidgetBuilder, WidgetContext, WidgetKey};
+
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
+pub struct Keyed {
+    #[builder(required)]
+    key: WidgetKey,
+    child: BoxedWidget,
+}
+
+impl Keyed {
+    pub fn new(key: impl Into<WidgetKey>, child: impl Into<BoxedWidget>) -> Self {
+        Keyed::builder(key, child)
+    }
+}
+
+impl Widget for Keyed {
+    fn key(&self) -> Option<&WidgetKey> {
+        Some(&self.key)
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(self.child.clone());
+    }
+}
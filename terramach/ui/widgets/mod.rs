@@ -19,14 +19,20 @@
 mod align;
 mod animated_opacity;
 mod aspect_ratio;
+mod baseline;
 mod column;
 mod constrained;
 mod decoration;
+mod error_boundary;
 mod fractional;
 mod gesture;
 mod image;
+mod intrinsic;
+mod keyed;
 mod opacity;
 mod padding;
+mod performance_overlay;
+mod repaint_boundary;
 mod row;
 mod scrollable;
 mod stack;
@@ -36,14 +42,20 @@ mod text_input;
 pub use align::*;
 pub use animated_opacity::*;
 pub use aspect_ratio::*;
+pub use baseline::*;
 pub use column::*;
 pub use constrained::*;
 pub use decoration::*;
+pub use error_boundary::*;
 pub use fractional::*;
 pub use gesture::*;
 pub use image::*;
+pub use intrinsic::*;
+pub use keyed::*;
 pub use opacity::*;
 pub use padding::*;
+pub use performance_overlay::*;
+pub use repaint_boundary::*;
 pub use row::*;
 pub use scrollable::*;
 pub use stack::*;
@@ -18,21 +18,19 @@
 
 use crate::{
     AnyWidget, BoxedWidget, BuildContext, Fit, HitTestContext, LayoutContext, MeasuredSize,
-    PaintContext, PartialWidget, Widget, WidgetContext,
+    PaintContext, PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Opacity {
+    #[builder(required)]
     opacity: f32,
     child: BoxedWidget,
 }
 
 impl Opacity {
     pub fn new(opacity: impl Into<f32>, child: impl Into<BoxedWidget>) -> Self {
-        Opacity {
-            opacity: opacity.into(),
-            child: child.into(),
-        }
+        Opacity::builder(opacity.into(), child)
     }
 }
 
@@ -17,13 +17,13 @@
  */
 
 use crate::{
-    AnyWidget, BoxedWidget, BuildContext, Constraints, HitTestContext, LayoutContext, MeasuredSize,
-    PaintContext, PartialWidget, Widget, WidgetContext,
+    AnyWidget, BoxedWidget, BuildContext, Constraints, HitTestContext, IntrinsicContext,
+    LayoutContext, MeasuredSize, PaintContext, PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
 use terramach_graphics::Size;
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Padding {
     left: f32,
     top: f32,
@@ -39,13 +39,11 @@ impl Padding {
         right: impl Into<Option<f32>>,
         bottom: impl Into<Option<f32>>,
     ) -> Self {
-        Padding {
-            left: left.into().unwrap_or_default(),
-            top: top.into().unwrap_or_default(),
-            right: right.into().unwrap_or_default(),
-            bottom: bottom.into().unwrap_or_default(),
-            child: None,
-        }
+        Padding::builder()
+            .with_left(left.into().unwrap_or_default())
+            .with_top(top.into().unwrap_or_default())
+            .with_right(right.into().unwrap_or_default())
+            .with_bottom(bottom.into().unwrap_or_default())
     }
 
     pub fn new(
@@ -55,13 +53,7 @@ impl Padding {
         bottom: impl Into<Option<f32>>,
         child: impl Into<BoxedWidget>,
     ) -> Self {
-        Padding {
-            left: left.into().unwrap_or_default(),
-            top: top.into().unwrap_or_default(),
-            right: right.into().unwrap_or_default(),
-            bottom: bottom.into().unwrap_or_default(),
-            child: Some(child.into()),
-        }
+        Padding::new_empty(left, top, right, bottom).with_child(child)
     }
 
     pub fn new_all(padding: impl Into<Option<f32>>, child: impl Into<BoxedWidget>) -> Self {
@@ -81,6 +73,26 @@ impl Padding {
 }
 
 impl Widget for Padding {
+    fn min_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        let vertical_padding = self.top + self.bottom;
+        intrinsic.min_intrinsic_width(0, (height - vertical_padding).max(0.0)) + self.left + self.right
+    }
+
+    fn max_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        let vertical_padding = self.top + self.bottom;
+        intrinsic.max_intrinsic_width(0, (height - vertical_padding).max(0.0)) + self.left + self.right
+    }
+
+    fn min_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        let horizontal_padding = self.left + self.right;
+        intrinsic.min_intrinsic_height(0, (width - horizontal_padding).max(0.0)) + self.top + self.bottom
+    }
+
+    fn max_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, width: f32) -> f32 {
+        let horizontal_padding = self.left + self.right;
+        intrinsic.max_intrinsic_height(0, (width - horizontal_padding).max(0.0)) + self.top + self.bottom
+    }
+
     fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let horizontal_padding = self.left + self.right;
         let vertical_padding = self.top + self.bottom;
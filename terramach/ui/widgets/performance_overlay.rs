@@ -0,0 +1,67 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use crate::{
+    AnyWidget, BoxedWidget, BuildContext, MountContext, PaintContext, PartialWidget, WidgetBuilder,
+    PerformanceOverlayLayer, Widget, WidgetContext,
+};
+use crate::gpu::PerformanceStats;
+use crate::widgets::Alignment;
+
+use terramach_graphics::{Rect, Size};
+
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
+pub struct PerformanceOverlay {
+    #[builder(default = "Alignment::top_right()")]
+    alignment: Alignment,
+    child: BoxedWidget,
+}
+
+impl PerformanceOverlay {
+    const WIDTH: f32 = 240.0;
+    const HEIGHT: f32 = 96.0;
+
+    pub fn new(child: impl Into<BoxedWidget>) -> Self {
+        PerformanceOverlay::builder(child)
+    }
+}
+
+impl Widget for PerformanceOverlay {
+    fn mount(&self, context: &mut WidgetContext, mount: &mut MountContext) {
+        context.set_state(mount.performance_stats());
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(self.child.clone());
+    }
+
+    fn paint(&self, context: &mut WidgetContext, paint: &mut PaintContext) {
+        paint.paint_children();
+        if let Some(stats) = context.state::<PerformanceStats>() {
+            let size = Size::new(
+                PerformanceOverlay::WIDTH.min(paint.size().width),
+                PerformanceOverlay::HEIGHT.min(paint.size().height),
+            );
+            let origin = self.alignment.align(paint.size(), size);
+            paint.push_layer(PerformanceOverlayLayer::new(
+                stats.clone(),
+                Rect::from_point_and_size(origin, size),
+            ));
+        }
+    }
+}
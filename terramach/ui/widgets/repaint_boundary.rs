@@ -0,0 +1,40 @@
+/*
+ * Terra Mach
+ * Copyright [2020] Terra Mach Authors
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program. If not, see <https://www.gnu.org/licenses/>
+ */
+
+use crate::{AnyWidget, BoxedWidget, BuildContext, PartialWidget, WidgetBuilder, Widget, WidgetContext};
+
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
+pub struct RepaintBoundary {
+    child: BoxedWidget,
+}
+
+impl RepaintBoundary {
+    pub fn new(child: impl Into<BoxedWidget>) -> Self {
+        RepaintBoundary::builder(child)
+    }
+}
+
+impl Widget for RepaintBoundary {
+    fn repaint_boundary(&self) -> bool {
+        true
+    }
+
+    fn build(&self, _: &mut WidgetContext, build: &mut BuildContext) {
+        build.add_child(self.child.clone());
+    }
+}
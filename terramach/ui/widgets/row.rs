@@ -19,13 +19,14 @@
 use terramach_graphics::Size;
 
 use crate::{
-    AnyWidget, BoxedWidget, BuildContext, Constraints, HitTestContext, LayoutContext, MeasuredSize,
-    PartialWidget, Widget, WidgetContext,
+    AnyWidget, BoxedWidget, BuildContext, Constraints, HitTestContext, IntrinsicContext,
+    IntrinsicDimension, LayoutContext, MeasuredSize, PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 use crate::widgets::{Flex, MainAxisAlignment, CrossAxisAlignment};
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Row {
+    #[builder(skip)]
     children: Vec<Flex>,
     horizontal_alignment: Option<MainAxisAlignment>,
     vertical_alignment: Option<CrossAxisAlignment>,
@@ -42,11 +43,9 @@ impl Row {
         horizontal_alignment: impl Into<Option<MainAxisAlignment>>,
         vertical_alignment: impl Into<Option<CrossAxisAlignment>>,
     ) -> Self {
-        Row {
-            children: Vec::new(),
-            horizontal_alignment: horizontal_alignment.into(),
-            vertical_alignment: vertical_alignment.into(),
-        }
+        Row::builder()
+            .with_horizontal_alignment(horizontal_alignment)
+            .with_vertical_alignment(vertical_alignment)
     }
 
     pub fn with_child(self, widget: impl Into<BoxedWidget>) -> Self {
@@ -64,6 +63,22 @@ impl Row {
 }
 
 impl Widget for Row {
+    fn min_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        intrinsic.sum_of_children(IntrinsicDimension::MinWidth, height)
+    }
+
+    fn max_intrinsic_width(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, height: f32) -> f32 {
+        intrinsic.sum_of_children(IntrinsicDimension::MaxWidth, height)
+    }
+
+    fn min_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, _: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MinHeight, f32::INFINITY)
+    }
+
+    fn max_intrinsic_height(&self, _: &WidgetContext, intrinsic: &IntrinsicContext, _: f32) -> f32 {
+        intrinsic.max_of_children(IntrinsicDimension::MaxHeight, f32::INFINITY)
+    }
+
     fn layout(&self, _: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let minimum_size = layout.constraints().minimum_size();
         let maximum_size = layout.constraints().maximum_size();
@@ -124,9 +139,28 @@ impl Widget for Row {
                 .constrain(&child_constraints);
         }
 
+        // align baselines
+        let mut baseline_offsets = vec![0.0; child_count];
+        let mut max_baseline: Option<f32> = None;
+        if self.vertical_alignment == Some(CrossAxisAlignment::Baseline) {
+            for child in 0..child_count {
+                if let Some(baseline) = layout.child_baseline(child) {
+                    max_baseline = Some(max_baseline.map_or(baseline, |max| max.max(baseline)));
+                }
+            }
+            if let Some(max_baseline) = max_baseline {
+                for child in 0..child_count {
+                    if let Some(baseline) = layout.child_baseline(child) {
+                        baseline_offsets[child] = max_baseline - baseline;
+                    }
+                }
+            }
+        }
+        layout.set_baseline(max_baseline);
+
         let content_size = Size::new(
             sizes.iter().map(|s| s.width).sum(),
-            sizes.iter().fold(0.0, |v, s| v.max(s.height)),
+            (0..child_count).fold(0.0, |v, child| v.max(sizes[child].height + baseline_offsets[child])),
         );
         let size = content_size.constrain(layout.constraints());
 
@@ -148,6 +182,7 @@ impl Widget for Row {
                     CrossAxisAlignment::Start | CrossAxisAlignment::Stretch => 0.0,
                     CrossAxisAlignment::Middle => (size.height - child_size.height) / 2.0,
                     CrossAxisAlignment::End => size.height - child_size.height,
+                    CrossAxisAlignment::Baseline => baseline_offsets[child],
                 }
             } else {
                 0.0
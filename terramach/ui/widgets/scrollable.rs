@@ -62,6 +62,10 @@ impl Scrollable {
 }
 
 impl Widget for Scrollable {
+    fn repaint_boundary(&self) -> bool {
+        true
+    }
+
     fn mount(&self, context: &mut WidgetContext, _: &mut MountContext) {
         context.set_state(ScrollableState::new());
     }
@@ -154,9 +158,10 @@ impl Widget for Scrollable {
                 }
             }
             Event::Frame(_) => {
+                let clock = context.clock();
                 let state = context.state_mut::<ScrollableState>().unwrap();
                 if let Some(animation) = &mut state.scrollbar_animation {
-                    if animation.advance() {
+                    if animation.advance(&clock) {
                         context.request_frame();
                     } else {
                         state.scrollbar_animation = None;
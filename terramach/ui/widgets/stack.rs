@@ -18,24 +18,17 @@
 
 use crate::{
     AnyWidget, BoxedWidget, BuildContext, Constraints, HitTestContext, LayoutContext, MeasuredSize,
-    PartialWidget, Widget, WidgetContext,
+    PartialWidget, WidgetBuilder, Widget, WidgetContext,
 };
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Stack {
     children: Vec<BoxedWidget>,
 }
 
 impl Stack {
     pub fn new() -> Self {
-        Stack {
-            children: Vec::new(),
-        }
-    }
-
-    pub fn with_child(mut self, widget: impl Into<BoxedWidget>) -> Self {
-        self.children.push(widget.into());
-        self
+        Stack::builder()
     }
 }
 
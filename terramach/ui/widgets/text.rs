@@ -16,24 +16,26 @@
  * along with this program. If not, see <https://www.gnu.org/licenses/>
  */
 
+use std::cell::RefCell;
 use std::fmt::{Debug, Formatter};
 use std::hash::{Hash, Hasher};
 use std::time::Duration;
 
-use crate::{AnyWidget, BoxedWidget, BuildContext, LayoutContext, MeasuredSize, MountContext, PaintContext, PartialWidget, Widget, WidgetContext, UpdateContext, EventContext, HitTestContext, Event, Animation, Id, Timer, Animator, Tween, AnimationCurves, KeyAction};
+use crate::{AnyWidget, BoxedWidget, BuildContext, IntrinsicContext, LayoutContext, MeasuredSize, MountContext, PaintContext, PartialWidget, Widget, WidgetBuilder, WidgetContext, UpdateContext, EventContext, HitTestContext, Event, Animation, Id, Timer, Animator, Tween, AnimationCurves, KeyAction};
 
 use terramach_graphics::textlayout::{
     FontCollection, Paragraph, ParagraphBuilder, ParagraphStyle, TextStyle as GrTextStyle, TextAlign,
 };
 use terramach_graphics::{Color, Rect, Color4f, FontMgr, FontStyle, Paint, Point, Size, Typeface};
 
-#[derive(Default, Clone)]
+#[derive(Default, Clone, WidgetBuilder)]
 pub struct TextStyle {
     background_color: Option<Color>,
     color: Option<Color>,
     font_size: Option<f32>,
     font_style: Option<FontStyle>,
     typeface: Option<Typeface>,
+    #[builder(skip)]
     font_families: Vec<String>,
     hint_color: Option<Color>,
     highlight_color: Option<Color>,
@@ -45,41 +47,6 @@ impl TextStyle {
     const DEFAULT_HIGHLIGHT_COLOR: Color = Color::new(0xFF808080);
     const DEFAULT_FONT_SIZE: f32 = 14.0;
 
-    pub fn with_background_color(mut self, background_color: impl Into<Option<Color>>) -> Self {
-        self.background_color = background_color.into();
-        self
-    }
-
-    pub fn with_color(mut self, color: impl Into<Option<Color>>) -> Self {
-        self.color = color.into();
-        self
-    }
-
-    pub fn with_hint_color(mut self, color: impl Into<Option<Color>>) -> Self {
-        self.hint_color = color.into();
-        self
-    }
-
-    pub fn with_highlight_color(mut self, color: impl Into<Option<Color>>) -> Self {
-        self.highlight_color = color.into();
-        self
-    }
-
-    pub fn with_font_size(mut self, font_size: impl Into<Option<f32>>) -> Self {
-        self.font_size = font_size.into();
-        self
-    }
-
-    pub fn with_font_style(mut self, font_style: impl Into<Option<FontStyle>>) -> Self {
-        self.font_style = font_style.into();
-        self
-    }
-
-    pub fn with_typeface(mut self, typeface: impl Into<Option<Typeface>>) -> Self {
-        self.typeface = typeface.into();
-        self
-    }
-
     pub fn with_font_families(mut self, font_families: &[impl AsRef<str>]) -> Self {
         self.font_families = font_families
             .iter()
@@ -228,7 +195,7 @@ impl Hash for TextStyle {
     }
 }
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct DefaultTextStyle {
     text_style: TextStyle,
     child: BoxedWidget,
@@ -236,10 +203,7 @@ pub struct DefaultTextStyle {
 
 impl DefaultTextStyle {
     pub fn new(text_style: impl Into<Option<TextStyle>>, child: impl Into<BoxedWidget>) -> Self {
-        DefaultTextStyle {
-            text_style: text_style.into().unwrap_or_default(),
-            child: child.into(),
-        }
+        DefaultTextStyle::builder(child).with_text_style(text_style.into().unwrap_or_default())
     }
 
     pub fn text_style(&self) -> &TextStyle {
@@ -253,7 +217,7 @@ impl Widget for DefaultTextStyle {
     }
 }
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct Text {
     text: String,
     text_style: Option<TextStyle>,
@@ -264,17 +228,13 @@ impl Text {
         text: impl Into<Option<T>>,
         text_style: impl Into<Option<TextStyle>>,
     ) -> Self {
-        Text {
-            text: text.into().map(|s| s.as_ref().to_string()).unwrap_or_default(),
-            text_style: text_style.into(),
-        }
+        Text::builder()
+            .with_text(text.into().map(|s| s.as_ref().to_string()).unwrap_or_default())
+            .with_text_style(text_style)
     }
 
     pub fn new_empty() -> Self {
-        Text {
-            text: String::default(),
-            text_style: None,
-        }
+        Text::builder()
     }
 
     pub fn new_text<T: AsRef<str>>(text: impl Into<Option<T>>) -> Self {
@@ -284,15 +244,8 @@ impl Text {
         )
     }
 
-    pub fn with_text_style(mut self, text_style: TextStyle) -> Self {
-        self.text_style = Some(text_style);
-        self
-    }
-
-    fn make_font_collection(&self) -> FontCollection {
-        let mut font_collection = FontCollection::new();
-        font_collection.set_default_font_manager(FontMgr::new(), None);
-        font_collection
+    pub fn text(&self) -> &str {
+        &self.text
     }
 
     fn make_text_style(&self, text_style: &TextStyle) -> GrTextStyle {
@@ -317,7 +270,7 @@ impl Text {
 impl Widget for Text {
     fn mount(&self, context: &mut WidgetContext, mount: &mut MountContext) {
         let mut text_style = self.text_style.clone().unwrap_or_default();
-        if let Some(default_text_style) = mount.ancestor_widget::<DefaultTextStyle>() {
+        if let Some(default_text_style) = mount.inherited_widget::<DefaultTextStyle>() {
             text_style = text_style.merge(default_text_style.text_style());
         }
 
@@ -329,38 +282,56 @@ impl Widget for Text {
 
     fn update(&self, context: &mut WidgetContext, update: &mut UpdateContext) {
         let mut text_style = self.text_style.clone().unwrap_or_default();
-        if let Some(default_text_style) = update.ancestor_widget::<DefaultTextStyle>() {
+        if let Some(default_text_style) = update.inherited_widget::<DefaultTextStyle>() {
             text_style = text_style.merge(default_text_style.text_style());
         }
 
         let state = context.state_mut::<TextState>().unwrap();
-        state.data = self.text.clone();
-        state.gr_text_style = self.make_text_style(&text_style);
+        state.set_text(&self.text, self.make_text_style(&text_style));
     }
 
-    fn layout(&self, context: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
-        let state = context.state_mut::<TextState>().unwrap();
+    fn min_intrinsic_width(&self, context: &WidgetContext, _: &IntrinsicContext, _: f32) -> f32 {
+        context.state::<TextState>().map_or(0.0, |state| {
+            state.with_paragraph(f32::INFINITY, |paragraph| paragraph.min_intrinsic_width())
+        })
+    }
 
-        let mut paragraph_builder =
-            ParagraphBuilder::new(&state.paragraph_style, self.make_font_collection());
-        paragraph_builder.push_style(&state.gr_text_style);
-        paragraph_builder.add_text(state.data());
+    fn max_intrinsic_width(&self, context: &WidgetContext, _: &IntrinsicContext, _: f32) -> f32 {
+        context.state::<TextState>().map_or(0.0, |state| {
+            state.with_paragraph(f32::INFINITY, |paragraph| paragraph.max_intrinsic_width())
+        })
+    }
 
-        let mut paragraph = paragraph_builder.build();
-        paragraph.layout(layout.constraints().maximum_size().width);
+    fn min_intrinsic_height(&self, context: &WidgetContext, _: &IntrinsicContext, width: f32) -> f32 {
+        context.state::<TextState>().map_or(0.0, |state| {
+            state.with_paragraph(width, |paragraph| paragraph.height())
+        })
+    }
 
-        let size = Size::new(
-            paragraph.max_intrinsic_width(),
-            paragraph.height(),
-        ).constrain(layout.constraints());
-        state.paragraph = Some(paragraph);
-        size
+    fn max_intrinsic_height(&self, context: &WidgetContext, _: &IntrinsicContext, width: f32) -> f32 {
+        context.state::<TextState>().map_or(0.0, |state| {
+            state.with_paragraph(width, |paragraph| paragraph.height())
+        })
     }
 
-    fn paint(&self, context: &mut WidgetContext, paint: &mut PaintContext) {
+    fn layout(&self, context: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let state = context.state_mut::<TextState>().unwrap();
-        if let Some(paragraph) = &mut state.paragraph {
-            paragraph.paint(paint.canvas(), Point::default());
+        let width = layout.constraints().maximum_size().width;
+        let (size, baseline) = state.with_paragraph(width, |paragraph| {
+            (
+                Size::new(paragraph.max_intrinsic_width(), paragraph.height()),
+                paragraph.alphabetic_baseline(),
+            )
+        });
+        layout.set_baseline(baseline);
+        state.layout_width = Some(width);
+        size.constrain(layout.constraints())
+    }
+
+    fn paint(&self, context: &mut WidgetContext, paint: &mut PaintContext) {
+        let state = context.state::<TextState>().unwrap();
+        if let Some(width) = state.layout_width {
+            state.with_paragraph(width, |paragraph| paragraph.paint(paint.canvas(), Point::default()));
         }
     }
 }
@@ -369,24 +340,64 @@ struct TextState {
     data: String,
     paragraph_style: ParagraphStyle,
     gr_text_style: GrTextStyle,
-    paragraph: Option<Paragraph>,
+    font_collection: FontCollection,
+    paragraphs: RefCell<Vec<(f32, Paragraph)>>,
+    layout_width: Option<f32>,
 }
 
 impl TextState {
+    const MAX_PARAGRAPHS: usize = 4;
+
     pub fn new(
         data: impl AsRef<str>,
         paragraph_style: ParagraphStyle,
         gr_text_style: GrTextStyle,
     ) -> Self {
+        let mut font_collection = FontCollection::new();
+        font_collection.set_default_font_manager(FontMgr::new(), None);
         TextState {
             data: data.as_ref().to_string(),
             paragraph_style,
             gr_text_style,
-            paragraph: None,
+            font_collection,
+            paragraphs: RefCell::new(Vec::new()),
+            layout_width: None,
         }
     }
 
     pub fn data(&self) -> &str {
         self.data.as_str()
     }
+
+    fn set_text(&mut self, data: impl AsRef<str>, gr_text_style: GrTextStyle) {
+        self.data = data.as_ref().to_string();
+        self.gr_text_style = gr_text_style;
+        self.paragraphs.borrow_mut().clear();
+    }
+
+    fn with_paragraph<R>(&self, width: f32, f: impl FnOnce(&mut Paragraph) -> R) -> R {
+        let mut paragraphs = self.paragraphs.borrow_mut();
+        let index = match paragraphs.iter().position(|(paragraph_width, _)| *paragraph_width == width) {
+            Some(index) => index,
+            None => {
+                if paragraphs.len() == TextState::MAX_PARAGRAPHS {
+                    paragraphs.remove(0);
+                }
+                paragraphs.push((width, self.make_paragraph(width)));
+                paragraphs.len() - 1
+            }
+        };
+        f(&mut paragraphs[index].1)
+    }
+
+    fn make_paragraph(&self, width: f32) -> Paragraph {
+        let mut paragraph_builder =
+            ParagraphBuilder::new(&self.paragraph_style, self.font_collection.clone());
+        paragraph_builder.push_style(&self.gr_text_style);
+        paragraph_builder.add_text(self.data());
+
+        let mut paragraph = paragraph_builder.build();
+        paragraph.layout(width);
+        paragraph
+    }
 }
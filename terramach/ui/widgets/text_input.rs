@@ -20,7 +20,7 @@ use std::fmt::{Debug, Formatter};
 use std::time::{Duration, Instant};
 
 use crate::{
-    BoxedWidget, LayoutContext, MeasuredSize, MountContext, PaintContext, PartialWidget, Widget,
+    BoxedWidget, IntrinsicContext, LayoutContext, MeasuredSize, MountContext, PaintContext, PartialWidget, Widget, WidgetBuilder,
     WidgetContext, UpdateContext, EventContext, HitTestContext, Event, Animation, Timer,
     Animator, Tween, AnimationCurves, Key, KeyAction,
 };
@@ -33,12 +33,15 @@ use terramach_graphics::textlayout::{
 };
 use terramach_graphics::{Color, Rect, Color4f, FontMgr, Paint, Point, Size};
 
-#[derive(Clone, PartialEq, PartialWidget)]
+#[derive(Clone, PartialEq, PartialWidget, WidgetBuilder)]
 pub struct TextInput {
     text: String,
+    #[builder(strip_option)]
     hint: Option<String>,
     text_style: Option<TextStyle>,
+    #[builder(default = "TextAlign::Start")]
     text_align: TextAlign,
+    #[builder(default = "1")]
     max_lines: usize,
 }
 
@@ -55,49 +58,23 @@ impl TextInput {
         hint: impl Into<Option<H>>,
         max_lines: impl Into<Option<usize>>,
     ) -> Self {
-        TextInput {
-            text: text.into().map(|s| s.as_ref().to_string()).unwrap_or_default(),
-            text_style: text_style.into(),
-            text_align: text_align.into().unwrap_or(TextAlign::Start),
-            hint: hint.into().map(|s| s.as_ref().to_string()),
-            max_lines: max_lines.into().unwrap_or(1),
+        let mut input = TextInput::new_text(text).with_text_style(text_style);
+        input.hint = hint.into().map(|s| s.as_ref().to_string());
+        if let Some(text_align) = text_align.into() {
+            input.text_align = text_align;
         }
-    }
-
-    pub fn new_empty() -> Self {
-        TextInput {
-            text: String::default(),
-            text_style: None,
-            text_align: TextAlign::Start,
-            hint: None,
-            max_lines: 1,
+        if let Some(max_lines) = max_lines.into() {
+            input.max_lines = max_lines;
         }
-    }
-
-    pub fn new_text<T: AsRef<str>>(text: impl Into<Option<T>>) -> Self {
-        let mut input = TextInput::new_empty();
-        input.text = text.into().map(|s| s.as_ref().to_string()).unwrap_or_default();
         input
     }
 
-    pub fn with_hint(mut self, hint: impl AsRef<str>) -> Self {
-        self.hint = hint.as_ref().to_string().into();
-        self
-    }
-
-    pub fn with_text_align(mut self, text_align: TextAlign) -> Self {
-        self.text_align = text_align;
-        self
-    }
-
-    pub fn with_text_style(mut self, text_style: TextStyle) -> Self {
-        self.text_style = Some(text_style);
-        self
+    pub fn new_empty() -> Self {
+        TextInput::builder()
     }
 
-    pub fn with_max_lines(mut self, max_lines: usize) -> Self {
-        self.max_lines = max_lines;
-        self
+    pub fn new_text<T: AsRef<str>>(text: impl Into<Option<T>>) -> Self {
+        TextInput::builder().with_text(text.into().map(|s| s.as_ref().to_string()).unwrap_or_default())
     }
 
     fn make_font_collection(&self) -> FontCollection {
@@ -128,7 +105,7 @@ impl TextInput {
 impl Widget for TextInput {
     fn mount(&self, context: &mut WidgetContext, mount: &mut MountContext) {
         let mut text_style = self.text_style.clone().unwrap_or_default();
-        if let Some(default_text_style) = mount.ancestor_widget::<DefaultTextStyle>() {
+        if let Some(default_text_style) = mount.inherited_widget::<DefaultTextStyle>() {
             text_style = text_style.merge(default_text_style.text_style());
         }
 
@@ -150,7 +127,7 @@ impl Widget for TextInput {
 
     fn update(&self, context: &mut WidgetContext, update: &mut UpdateContext) {
         let mut text_style = self.text_style.clone().unwrap_or_default();
-        if let Some(default_text_style) = update.ancestor_widget::<DefaultTextStyle>() {
+        if let Some(default_text_style) = update.inherited_widget::<DefaultTextStyle>() {
             text_style = text_style.merge(default_text_style.text_style());
         }
 
@@ -167,10 +144,30 @@ impl Widget for TextInput {
         state.invalidate();
     }
 
+    fn min_intrinsic_width(&self, _: &WidgetContext, _: &IntrinsicContext, _: f32) -> f32 {
+        0.0
+    }
+
+    fn max_intrinsic_width(&self, context: &WidgetContext, _: &IntrinsicContext, _: f32) -> f32 {
+        context.state::<TextState>()
+            .map_or(0.0, |state| state.measure_paragraph().max_intrinsic_width())
+    }
+
+    fn min_intrinsic_height(&self, context: &WidgetContext, _: &IntrinsicContext, _: f32) -> f32 {
+        context.state::<TextState>()
+            .map_or(0.0, |state| state.measure_paragraph().height())
+    }
+
+    fn max_intrinsic_height(&self, context: &WidgetContext, _: &IntrinsicContext, _: f32) -> f32 {
+        context.state::<TextState>()
+            .map_or(0.0, |state| state.measure_paragraph().height())
+    }
+
     fn layout(&self, context: &mut WidgetContext, layout: &mut LayoutContext) -> Size {
         let state = context.state_mut::<TextState>().unwrap();
         // state.max_width = layout.constraints().maximum_size().width;
         state.invalidate();
+        layout.set_baseline(state.paragraph_baseline());
         Size::new_unbound_width(state.paragraph_height().unwrap())
             .constrain(layout.constraints())
     }
@@ -230,9 +227,10 @@ impl Widget for TextInput {
                 }
             }
             Event::Frame(_) => {
+                let clock = context.clock();
                 let state = context.state_mut::<TextState>().unwrap();
                 if let Some(caret_bounds) = &mut state.caret_bounds {
-                    if caret_bounds.advance() {
+                    if caret_bounds.advance(&clock) {
                         context.request_frame();
                     } else {
                         state.caret_bounds = None;
@@ -242,7 +240,7 @@ impl Widget for TextInput {
 
                 let state = context.state_mut::<TextState>().unwrap();
                 if let Some(caret_opacity) = &mut state.caret_opacity {
-                    if caret_opacity.advance() {
+                    if caret_opacity.advance(&clock) {
                         context.request_frame();
                     } else {
                         state.caret_active = !state.caret_active;
@@ -828,6 +826,24 @@ impl TextState {
         });
     }
 
+    pub fn measure_paragraph(&self) -> Paragraph {
+        let text = self.selection.text();
+        let mut paragraph = ParagraphBuilder::new(&self.paragraph_style, self.font_collection.clone())
+            .push_style(&self.text_style)
+            .add_text(if text.is_empty() { " " } else { text })
+            .build();
+        paragraph.layout(f32::INFINITY);
+        paragraph
+    }
+
+    pub fn paragraph_baseline(&self) -> Option<f32> {
+        if self.selection.text().is_empty() {
+            self.empty_paragraph.as_ref()?.alphabetic_baseline().into()
+        } else {
+            self.paragraph.as_ref()?.alphabetic_baseline().into()
+        }
+    }
+
     pub fn paragraph_height(&self) -> Option<f32> {
         if self.selection.text().is_empty() {
             self.empty_paragraph.as_ref()?.height().into()